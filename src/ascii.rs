@@ -1,81 +1,98 @@
-use crate::info::os_pretty_name;
-use std::collections::HashMap;
+use crate::info::os_release;
 
-pub fn ascii_test() {
-    let distros = [
-        "Arch Linux",
-        "Debian",
-        "Fedora",
-        "EndeavourOS",
-        "Void",
-        "Ubuntu",
-        "Suse",
-        "Raspbian",
-        "Linux Mint",
-        "MX Linux",
-        "Gentoo",
-        "Funtoo",
-        "Slackware",
-        "UwUntu",
-        "NixOS",
-        "VanillaOS",
-        "Kali Linux",
-        "CachyOS",
-        "NetBSD",
-        "FreeBSD",
-        "Unknown distro",
-    ];
+/// A built-in logo. `${c1}` to `${c6}` in the ASCII switch to the matching color of the palette,
+/// text before the first placeholder uses the first color.
+#[derive(Clone, Copy, Debug)]
+pub struct Logo {
+    pub ascii: &'static str,
+    pub palette: &'static [&'static str],
+}
+
+/// Shown for distributions without a logo of their own.
+pub const BANNER: Logo = Logo {
+    ascii: "   ___      _____      __ \n  / _ \\___ / _/ /_____/ / \n / , _(_-</ _/ __/ __/ _ \\\n/_/|_/___/_/ \\__/\\__/_//_/",
+    palette: &[],
+};
+
+/// Every built-in logo with the names it is found by: os-release IDs first, then the names
+/// `--override` accepts.
+const LOGOS: &[(&[&str], Logo)] = &[
+    (&["arch", "Arch Linux", "archarm"], Logo { ascii: "   ___               __ \n  / _ |  ____ ____  / / \n / __ | / __// __/ / _ \\\n/_/ |_|/_/   \\__/ /_//_/", palette: &["#1793d1"] }),
+    (&["debian"], Logo { ascii: "   ___      __   _         \n  / _ \\___ / /  (_)__ ____ \n / // / -_) _ \\/ / _ `/ _ \\\n/____/\\__/_.__/_/\\_,_/_//_/", palette: &["#d70a53"] }),
+    (&["fedora"], Logo { ascii: "   ____       __             \n  / __/__ ___/ /__  _______ _\n / _// -_) _  / _ \\/ __/ _ `/\n/_/  \\__/\\_,_/\\___/_/  \\_,_/", palette: &["#51a2da"] }),
+    (&["endeavouros", "endeavour"], Logo { ascii: "   ____        __                           \n  / __/__  ___/ /__ ___ __  _____  __ ______\n / _// _ \\/ _  / -_) _ `/ |/ / _ \\/ // / __/\n/___/_//_/\\_,_/\\__/\\_,_/|___/\\___/\\_,_/_/", palette: &["#7f3fbf"] }),
+    (&["void", "Void Linux"], Logo { ascii: "  _   __     _    __\n | | / /__  (_)__/ /\n | |/ / _ \\/ / _  / \n |___/\\___/_/\\_,_/", palette: &["#478061"] }),
+    (&["ubuntu"], Logo { ascii: "  __  ____             __      \n / / / / /  __ _____  / /___ __\n/ /_/ / _ \\/ // / _ \\/ __/ // /\n\\____/_.__/\\_,_/_//_/\\__/\\_,_/", palette: &["#e95420"] }),
+    (&["opensuse", "opensuse-tumbleweed", "opensuse-leap", "opensuse-microos", "opensuse-aeon", "opensuse-kalpa", "opensuse-leap-micro", "opensuse-slowroll", "sles", "sled", "suse"], Logo { ascii: "  ____                ____            \n / __ \\___  ___ ___  / __/_ _____ ___ \n/ /_/ / _ \\/ -_) _ \\_\\ \\/ // (_-</ -_)\n\\____/ .__/\\__/_//_/___/\\_,_/___/\\__/ \n    /_/", palette: &["#73ba25"] }),
+    (&["raspbian", "rasp"], Logo { ascii: "${c1}   ___              ${c2}  __   _         \n${c1}  / _ \\___ ____ ___ ${c2} / /  (_)__ ____ \n${c1} / , _/ _ `(_-</ _ \\${c2}/ _ \\/ / _ `/ _ \\\n${c1}/_/|_|\\_,_/___/ .__/${c2}_.__/_/\\_,_/_//_/\n${c1}             /_/", palette: &["#c51a4a", "#6cc04a"] }),
+    (&["linuxmint", "Linux Mint", "mint"], Logo { ascii: "   __  ____      __ \n  /  |/  (_)__  / /_\n / /|_/ / / _ \\/ __/\n/_/  /_/_/_//_/\\__/", palette: &["#87cf3e"] }),
+    (&["mx", "MX Linux"], Logo { ascii: "${c1}   __  ____  __${c2}  __   _               \n${c1}  /  |/  / |/_/${c2} / /  (_)__  __ ____ __\n${c1} / /|_/ />  < ${c2} / /__/ / _ \\/ // /\\ \\ /\n${c1}/_/  /_/_/|_|${c2} /____/_/_//_/\\_,_//_\\_\\", palette: &["white", "#2c6fb4"] }),
+    (&["gentoo"], Logo { ascii: "  _____         __          \n / ___/__ ___  / /____  ___ \n/ (_ / -_) _ \\/ __/ _ \\/ _ \\\n\\___/\\__/_//_/\\__/\\___/\\___/", palette: &["#9f94d7"] }),
+    (&["funtoo"], Logo { ascii: "   ____          __          \n  / __/_ _____  / /____  ___ \n / _// // / _ \\/ __/ _ \\/ _ \n/_/  \\_,_/_//_/\\__/\\___/\\___/", palette: &["magenta"] }),
+    (&["slackware", "slack"], Logo { ascii: "   ______         __                      \n  / __/ /__ _____/ /___    _____ ________ \n _\\ \\/ / _ `/ __/  '_/ |/|/ / _ `/ __/ -_)\n/___/_/\\_,_/\\__/_/\\_\\|__,__/\\_,_/_/  \\__/ ", palette: &["#6272a4"] }),
+    (&["uwuntu"], Logo { ascii: "  __  __       __  __     __      \n / / / /    __/ / / /__  / /___ __\n/ /_/ / |/|/ / /_/ / _ \\/ __/ // /\n\\____/|__,__/\\____/_//_/\\__/\\_,_/", palette: &["#ff9de2"] }),
+    (&["nixos", "nix"], Logo { ascii: "${c1}   _  ___    ${c2}  ____  ____\n${c1}  / |/ (_)_ __${c2}/ __ \\/ __/\n${c1} /    / /\\ \\ /${c2} /_/ /\\ \\  \n${c1}/_/|_/_//_\\_\\${c2}\\____/___/", palette: &["#7ebae4", "#5277c3"] }),
+    (&["vanilla", "VanillaOS"], Logo { ascii: "  _   __          _ ____    \n | | / /__ ____  (_) / /__ _\n | |/ / _ `/ _ \\/ / / / _ `/\n |___/\\_,_/_//_/_/_/_/\\_,_/", palette: &["#f5c211"] }),
+    (&["kali", "Kali Linux"], Logo { ascii: "   __ __     ___   \n  / //_/__ _/ (_)  \n / ,< / _ `/ / /   \n/_/|_|\\_,_/_/_/", palette: &["#367bf0"] }),
+    (&["cachyos", "cachy"], Logo { ascii: "  _____         __       \n / ___/__ _____/ /  __ __\n/ /__/ _ `/ __/ _ \\/ // /\n\\___/\\_,_/\\__/_//_/\\_, / \n                  /___/", palette: &["#00b5a6"] }),
+    (&["netbsd"], Logo { ascii: "${c1}   _  __    __ ${c2} ___  _______ \n${c1}  / |/ /__ / /_${c2}/ _ )/ __/ _ \\\n${c1} /    / -_) __/${c2} _  |\\ \\/ // /\n${c1}/_/|_/\\__/\\__/${c2}____/___/____/ ", palette: &["#f26711", "white"] }),
+    (&["freebsd"], Logo { ascii: "${c1}   ___           ${c2} ___  _______ \n${c1}  / _/______ ___${c2} / _ )/ __/ _ \\\n${c1} / _/ __/ -_) -_)${c2} _  |\\ \\/ // /\n${c1}/_//_/  \\__/\\__/${c2}____/___/____/", palette: &["#ab2b28", "white"] }),
+    (&["alpine", "Alpine Linux"], Logo { ascii: "   ___   __     _         \n  / _ | / /__  (_)__  ___ \n / __ |/ / _ \\/ / _ \\/ -_)\n/_/ |_/_/ .__/_/_//_/\\__/ \n       /_/", palette: &["#0d597f"] }),
+    (&["manjaro", "manjaro-arm"], Logo { ascii: "   __  ___          _             \n  /  |/  /__ ____  (_)__ _____ ___ \n / /|_/ / _ `/ _ \\/ / _ `/ __/ _ \\\n/_/  /_/\\_,_/_//_/ /\\_,_/_/  \\___/\n              /___/", palette: &["#35bf5c"] }),
+    (&["centos"], Logo { ascii: "  _____         __  ____  ____\n / ___/__ ___  / /_/ __ \\/ __/\n/ /__/ -_) _ \\/ __/ /_/ /\\ \\  \n\\___/\\__/_//_/\\__/\\____/___/  ", palette: &["#932279"] }),
+    (&["rocky", "Rocky Linux"], Logo { ascii: "   ___           __       \n  / _ \\___  ____/ /____ __\n / , _/ _ \\/ __/  '_/ // /\n/_/|_|\\___/\\__/_/\\_\\\\_, / \n                   /___/  ", palette: &["#10b981"] }),
+    (&["almalinux", "alma"], Logo { ascii: "   ___   __          \n  / _ | / /_ _  ___ _\n / __ |/ /  ' \\/ _ `/\n/_/ |_/_/_/_/_/\\_,_/ ", palette: &["#0069da"] }),
+    (&["artix", "Artix Linux"], Logo { ascii: "   ___       __  _     \n  / _ | ____/ /_(_)_ __\n / __ |/ __/ __/ /\\ \\ /\n/_/ |_/_/  \\__/_//_\\_\\", palette: &["#10a0cc"] }),
+    (&["solus"], Logo { ascii: "   ____     __       \n  / __/__  / /_ _____\n _\\ \\/ _ \\/ / // (_-<\n/___/\\___/_/\\_,_/___/", palette: &["#5294e2"] }),
+    (&["rhel", "Red Hat Enterprise Linux", "redhat"], Logo { ascii: "   ___  __ ________ \n  / _ \\/ // / __/ / \n / , _/ _  / _// /__\n/_/|_/_//_/___/____/", palette: &["#ee0000"] }),
+    (&["zorin", "Zorin OS"], Logo { ascii: "  ____           _    \n /_  / ___  ____(_)__ \n  / /_/ _ \\/ __/ / _ \\\n /___/\\___/_/ /_/_//_/", palette: &["#15a6f0"] }),
+    (&["nobara"], Logo { ascii: "   _  __     __                \n  / |/ /__  / /  ___ ________ _\n /    / _ \\/ _ \\/ _ `/ __/ _ `/\n/_/|_/\\___/_.__/\\_,_/_/  \\_,_/", palette: &["#9a9a9a"] }),
+    (&["bazzite"], Logo { ascii: "   ___              _ __     \n  / _ )___ _______ (_) /____ \n / _  / _ `/_ /_ // / __/ -_)\n/____/\\_,_//__/__/_/\\__/\\__/", palette: &["#8a6fe8"] }),
+    (&["elementary", "elementary OS"], Logo { ascii: "      __                   __               \n ___ / /__ __ _  ___ ___  / /____ _______ __\n/ -_) / -_)  ' \\/ -_) _ \\/ __/ _ `/ __/ // /\n\\__/_/\\__/_/_/_/\\__/_//_/\\__/\\_,_/_/  \\_, / \n                                     /___/", palette: &["#64baff"] }),
+];
 
-    for i in distros {
-        println!("\n{i}: \n{}", get_distro_ascii(Some(i.to_string())));
+/// The names to look logos up by, most specific first: the lowercased `ascii_override`, or the
+/// os-release `ID` followed by every `ID_LIKE`.
+pub fn distro_ids(ascii_override: Option<String>) -> Vec<String> {
+    if let Some(ascii_override) = ascii_override {
+        return vec![ascii_override.to_lowercase()];
     }
+
+    os_release("ID")
+        .into_iter()
+        .chain(os_release("ID_LIKE"))
+        .flat_map(|ids| {
+            ids.split_whitespace()
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+        })
+        .collect()
 }
 
-pub fn get_distro_ascii(ascii_override: Option<String>) -> String {
-    let distros = HashMap::from([
-        ("arch", "   ___               __ \n  / _ |  ____ ____  / / \n / __ | / __// __/ / _ \\\n/_/ |_|/_/   \\__/ /_//_/"),
-        ("debian", "   ___      __   _         \n  / _ \\___ / /  (_)__ ____ \n / // / -_) _ \\/ / _ `/ _ \\\n/____/\\__/_.__/_/\\_,_/_//_/"),
-        ("fedora", "   ____       __             \n  / __/__ ___/ /__  _______ _\n / _// -_) _  / _ \\/ __/ _ `/\n/_/  \\__/\\_,_/\\___/_/  \\_,_/"),
-        ("endeavour", "   ____        __                           \n  / __/__  ___/ /__ ___ __  _____  __ ______\n / _// _ \\/ _  / -_) _ `/ |/ / _ \\/ // / __/\n/___/_//_/\\_,_/\\__/\\_,_/|___/\\___/\\_,_/_/"),
-        ("void", "  _   __     _    __\n | | / /__  (_)__/ /\n | |/ / _ \\/ / _  / \n |___/\\___/_/\\_,_/"),
-        ("ubuntu", "  __  ____             __      \n / / / / /  __ _____  / /___ __\n/ /_/ / _ \\/ // / _ \\/ __/ // /\n\\____/_.__/\\_,_/_//_/\\__/\\_,_/"),
-        ("suse", "  ____                ____            \n / __ \\___  ___ ___  / __/_ _____ ___ \n/ /_/ / _ \\/ -_) _ \\_\\ \\/ // (_-</ -_)\n\\____/ .__/\\__/_//_/___/\\_,_/___/\\__/ \n    /_/"),
-        ("rasp", "   ___                __   _         \n  / _ \\___ ____ ___  / /  (_)__ ____ \n / , _/ _ `(_-</ _ \\/ _ \\/ / _ `/ _ \\\n/_/|_|\\_,_/___/ .__/_.__/_/\\_,_/_//_/\n             /_/"),
-        ("mint", "   __  ____      __ \n  /  |/  (_)__  / /_\n / /|_/ / / _ \\/ __/\n/_/  /_/_/_//_/\\__/"),
-        ("mx", "   __  ____  __  __   _               \n  /  |/  / |/_/ / /  (_)__  __ ____ __\n / /|_/ />  <  / /__/ / _ \\/ // /\\ \\ /\n/_/  /_/_/|_| /____/_/_//_/\\_,_//_\\_\\"),
-        ("gentoo", "  _____         __          \n / ___/__ ___  / /____  ___ \n/ (_ / -_) _ \\/ __/ _ \\/ _ \\\n\\___/\\__/_//_/\\__/\\___/\\___/"),
-        ("funtoo", "   ____          __          \n  / __/_ _____  / /____  ___ \n / _// // / _ \\/ __/ _ \\/ _ \n/_/  \\_,_/_//_/\\__/\\___/\\___/"),
-        ("slack", "   ______         __                      \n  / __/ /__ _____/ /___    _____ ________ \n _\\ \\/ / _ `/ __/  '_/ |/|/ / _ `/ __/ -_)\n/___/_/\\_,_/\\__/_/\\_\\|__,__/\\_,_/_/  \\__/ "),
-        ("uwuntu", "  __  __       __  __     __      \n / / / /    __/ / / /__  / /___ __\n/ /_/ / |/|/ / /_/ / _ \\/ __/ // /\n\\____/|__,__/\\____/_//_/\\__/\\_,_/"),
-        ("nix", "   _  ___      ____  ____\n  / |/ (_)_ __/ __ \\/ __/\n /    / /\\ \\ / /_/ /\\ \\  \n/_/|_/_//_\\_\\\\____/___/"),
-        ("vanilla", "  _   __          _ ____    \n | | / /__ ____  (_) / /__ _\n | |/ / _ `/ _ \\/ / / / _ `/\n |___/\\_,_/_//_/_/_/_/\\_,_/"),
-        ("kali", "   __ __     ___   \n  / //_/__ _/ (_)  \n / ,< / _ `/ / /   \n/_/|_|\\_,_/_/_/"),
-        ("cachy", "  _____         __       \n / ___/__ _____/ /  __ __\n/ /__/ _ `/ __/ _ \\/ // /\n\\___/\\_,_/\\__/_//_/\\_, / \n                  /___/"),
-        ("netbsd", "   _  __    __  ___  _______ \n  / |/ /__ / /_/ _ )/ __/ _ \\\n /    / -_) __/ _  |\\ \\/ // /\n/_/|_/\\__/\\__/____/___/____/ "),
-        ("freebsd", "   ___            ___  _______ \n  / _/______ ___ / _ )/ __/ _ \\\n / _/ __/ -_) -_) _  |\\ \\/ // /\n/_//_/  \\__/\\__/____/___/____/")
-    ]);
+/// Compares names ignoring case, spaces and punctuation, so "Arch Linux", "arch-linux" and
+/// "archlinux" are the same.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
 
-    let search_term = ascii_override
-        .clone()
-        .map(|o| o.to_ascii_lowercase())
-        .unwrap_or_else(|| {
-            os_pretty_name(None, "ID")
-                .unwrap_or_default()
-                .to_ascii_lowercase()
-        });
+/// The built-in logo named exactly `id`, see [`LOGOS`] for the names.
+pub fn find_logo(id: &str) -> Option<Logo> {
+    let id = normalize(id);
 
-    let ascii_result = distros
+    LOGOS
         .iter()
-        .find_map(|(&key, &value)| {
-            if search_term.contains(key) {
-                Some(value.to_string())
-            } else {
-                None
-            }
-        })
-        .unwrap_or_else(|| {
-            "   ___      _____      __ \n  / _ \\___ / _/ /_____/ / \n / , _(_-</ _/ __/ __/ _ \\\n/_/|_/___/_/ \\__/\\__/_//_/".to_string()
-        });
+        .find(|(names, _)| names.iter().any(|name| normalize(name) == id))
+        .map(|&(_, logo)| logo)
+}
 
-    ascii_result
+/// Returns the logo for `ascii_override`, or for the running distribution when `None`, trying
+/// its `ID` before its `ID_LIKE`s. Distributions without one get the [`BANNER`].
+pub fn get_distro_logo(ascii_override: Option<String>) -> Logo {
+    distro_ids(ascii_override)
+        .iter()
+        .find_map(|id| find_logo(id))
+        .unwrap_or(BANNER)
 }
@@ -0,0 +1,124 @@
+use crate::config::{
+    canonical_module, describe_error, fetch_json_path, parse_color, parse_config,
+    DEFAULT_TIMEOUT_MS,
+};
+use crate::layout::LogoPosition;
+use colored::Colorize;
+use regex::Regex;
+use std::fs;
+
+/// Validates the config file, printing every problem found, and returns whether it had none.
+/// Aliases are only pointed out, they are not problems.
+pub(crate) fn check_config(custom_config_file: Option<String>) -> bool {
+    let path = fetch_json_path(custom_config_file);
+    let file = path.display();
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            println!("[{}] {file}: could not be read: {e}", "ERROR".red());
+            return false;
+        }
+    };
+
+    let info = match parse_config(&contents) {
+        Ok(info) => info,
+        Err(e) => {
+            println!("[{}] {}", "ERROR".red(), describe_error(&path, &e));
+            return false;
+        }
+    };
+
+    let mut problems = 0;
+    let mut warn = |message: String| {
+        println!("[{}] {file}: {message}", "WARNING".yellow());
+        problems += 1;
+    };
+
+    let custom_modules: Vec<String> = info.modules.keys().map(|k| k.to_lowercase()).collect();
+
+    for (section_idx, section) in info.info.iter().enumerate() {
+        for (entry_idx, entry) in section.iter().enumerate() {
+            let name = entry.module();
+            let location = format!("section {}, entry {}", section_idx + 1, entry_idx + 1);
+
+            match canonical_module(name) {
+                Some(canonical) if canonical != name.to_lowercase() => println!(
+                    "[{}] {file}: {location}: \"{name}\" is an alias for \"{canonical}\"",
+                    "NOTE".cyan()
+                ),
+                Some(_) => {}
+                None if custom_modules.contains(&name.to_lowercase()) => {}
+                None => warn(format!(
+                    "{location}: unknown module \"{name}\", it will be shown as blank"
+                )),
+            }
+        }
+    }
+
+    for (name, module) in &info.modules {
+        if let Some(canonical) = canonical_module(name) {
+            warn(format!(
+                "custom module \"{name}\" is hidden by the built-in \"{canonical}\" module"
+            ));
+        }
+
+        if module.command.is_none() && module.file.is_none() {
+            warn(format!(
+                "custom module \"{name}\" needs either a \"command\" or a \"file\""
+            ));
+        }
+
+        if let Some(Err(e)) = module.regex.as_deref().map(Regex::new) {
+            warn(format!(
+                "custom module \"{name}\" has an invalid regex: {e}"
+            ));
+        }
+    }
+
+    for color in &info.color {
+        if parse_color(color).is_none() {
+            warn(format!(
+                "unknown color \"{color}\", it will be shown as white"
+            ));
+        }
+    }
+
+    for color in info.logo_colors.iter().flatten() {
+        if parse_color(color).is_none() {
+            warn(format!(
+                "unknown logo color \"{color}\", it will be shown as white"
+            ));
+        }
+    }
+
+    if info.color.len() < info.info.len() + 1 {
+        warn(format!(
+            "{} colors for {} sections, there should be one for the logo plus one per section",
+            info.color.len(),
+            info.info.len()
+        ));
+    }
+
+    if let Some(position) = &info.logo_position {
+        if LogoPosition::parse(position).is_none() {
+            warn(format!(
+                "unknown logo position \"{position}\", expected top, left, right or none"
+            ));
+        }
+    }
+
+    let timeout = info.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    if let Some(usage) = info.cpu_usage.as_ref().filter(|u| u.interval >= timeout) {
+        warn(format!(
+            "the cpu usage interval of {}ms is not shorter than the {timeout}ms timeout, it will always be shown as N/A",
+            usage.interval
+        ));
+    }
+
+    if problems == 0 {
+        println!("[{}] {file} is valid.", "OK".green());
+    }
+
+    problems == 0
+}
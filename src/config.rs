@@ -1,15 +1,174 @@
-use colored::Color;
+use crate::custom::CustomModule;
+use crate::layout::LogoPosition;
+use colored::{Color, Colorize};
+use rsftch::info::NetworkInterface;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
+pub(crate) const DEFAULT_TIMEOUT_MS: u64 = 2000;
+
+/// Every built-in module with the names it can be referred to by in the config, the first
+/// name is the canonical one.
+pub(crate) const MODULES: &[&[&str]] = &[
+    &["os", "distro"],
+    &["host", "hostname"],
+    &["network", "net", "ip"],
+    &["shell"],
+    &["kernel"],
+    &["packs", "packages"],
+    &["user", "username"],
+    &["term", "terminal"],
+    &["de", "dewm", "wm"],
+    &["cpu", "processor"],
+    &["cpu-usage", "cpuusage", "usage"],
+    &["load", "loadavg"],
+    &["gpu", "graphics"],
+    &["mem", "memory"],
+    &["swap", "zram"],
+    &["uptime"],
+    &["res", "display", "resolution"],
+    &["time", "timezone"],
+    &["disk", "diskusage"],
+    &["battery", "bat"],
+];
+
+/// Resolves a module name or alias from the config to its canonical name.
+pub(crate) fn canonical_module(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    MODULES
+        .iter()
+        .find(|names| names.contains(&name.as_str()))
+        .map(|names| names[0])
+}
+
+/// A module listed in an info section, with optional overrides for how it is shown.
+#[derive(Clone, Deserialize)]
+pub(crate) struct ModuleConfig {
+    pub(crate) module: String,
+    pub(crate) title: Option<String>,
+    pub(crate) icon: Option<String>,
+    pub(crate) format: Option<String>,
+}
+
+/// Sections accept plain module names as well as full module objects.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ModuleEntry {
+    Name(String),
+    Config(ModuleConfig),
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Info {
+    pub(crate) info: Vec<Vec<ModuleEntry>>,
+    pub(crate) color: Vec<String>,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) logo_position: Option<String>,
+    #[serde(default)]
+    pub(crate) modules: HashMap<String, CustomModule>,
+    pub(crate) logo: Option<LogoConfig>,
+    pub(crate) logo_colors: Option<Vec<String>>,
+    pub(crate) network: Option<NetworkConfig>,
+    pub(crate) disk: Option<DiskConfig>,
+    pub(crate) cpu_usage: Option<CpuUsageConfig>,
+}
+
+/// How long the cpu-usage module watches the processor for, in milliseconds, and whether it
+/// shows a row for each CPU rather than the overall usage.
+#[derive(Clone, Deserialize)]
+pub(crate) struct CpuUsageConfig {
+    #[serde(default = "default_interval")]
+    pub(crate) interval: u64,
+    #[serde(default)]
+    pub(crate) per_core: bool,
+}
+
+impl Default for CpuUsageConfig {
+    fn default() -> Self {
+        CpuUsageConfig {
+            interval: default_interval(),
+            per_core: false,
+        }
+    }
+}
+
+fn default_interval() -> u64 {
+    200
+}
+
+/// The mount points the disk module shows, one row each.
+#[derive(Clone, Deserialize)]
+pub(crate) struct DiskConfig {
+    #[serde(default = "default_mounts")]
+    pub(crate) mounts: Vec<String>,
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        DiskConfig {
+            mounts: default_mounts(),
+        }
+    }
+}
+
+fn default_mounts() -> Vec<String> {
+    vec!["/".to_string(), "/home".to_string()]
+}
+
+/// Which interfaces the network module shows. Without a list, every interface that is not
+/// down and has an address is shown, except loopback and bridges unless asked for.
+#[derive(Clone, Default, Deserialize)]
+pub(crate) struct NetworkConfig {
+    pub(crate) interfaces: Option<Vec<String>>,
+    #[serde(default)]
+    pub(crate) show_loopback: bool,
+    #[serde(default)]
+    pub(crate) show_bridges: bool,
+}
+
+impl NetworkConfig {
+    /// The interfaces to show, in the configured order when there is a list.
+    pub(crate) fn select(&self, interfaces: Vec<NetworkInterface>) -> Vec<NetworkInterface> {
+        match &self.interfaces {
+            Some(names) => names
+                .iter()
+                .filter_map(|name| interfaces.iter().find(|i| i.name == *name).cloned())
+                .collect(),
+            None => interfaces
+                .into_iter()
+                .filter(|i| {
+                    i.state != "down"
+                        && !i.addresses.is_empty()
+                        && (self.show_loopback || !i.loopback)
+                        && (self.show_bridges || !i.bridge)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A custom logo, either inline (a string with newlines, or a list of lines) or a path to a
+/// text file, relative paths being resolved from the config's directory.
 #[derive(Deserialize)]
-struct Info {
-    info: Vec<Vec<String>>,
-    color: Vec<String>,
+#[serde(untagged)]
+pub(crate) enum LogoConfig {
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl ModuleEntry {
+    pub(crate) fn module(&self) -> &str {
+        match self {
+            ModuleEntry::Name(module) => module,
+            ModuleEntry::Config(config) => &config.module,
+        }
+    }
 }
 
-fn fetch_json_path(custom_config_file: Option<String>) -> PathBuf {
+pub(crate) fn fetch_json_path(custom_config_file: Option<String>) -> PathBuf {
     match custom_config_file.is_none() {
         true => dirs::config_dir().unwrap().join("rsftch/info.json"),
         false => PathBuf::from(custom_config_file.unwrap()),
@@ -30,21 +189,154 @@ fn default_json() -> String {
     .to_string()
 }
 
+pub(crate) fn parse_config(contents: &str) -> serde_json::Result<Info> {
+    serde_json::from_str(contents)
+}
+
+/// Describes a parse error as "path:line:column: message".
+pub(crate) fn describe_error(path: &Path, e: &serde_json::Error) -> String {
+    let message = e.to_string();
+    let suffix = format!(" at line {} column {}", e.line(), e.column());
+
+    format!(
+        "{}:{}:{}: {}",
+        path.display(),
+        e.line(),
+        e.column(),
+        message.strip_suffix(&suffix).unwrap_or(&message)
+    )
+}
+
+/// Reads the config, falling back to the default one when it is missing or invalid.
 fn fetch_json(custom_config_file: Option<String>, configuration_part: &str) -> Info {
+    static REPORTED: AtomicBool = AtomicBool::new(false);
+
     let json_path = fetch_json_path(custom_config_file.clone());
-    let contents = fs::read_to_string(json_path).unwrap_or(default_json());
-    serde_json::from_str(&contents).expect(format!("The {configuration_part} configuration is not valid, please read the README for further information or use an example listed in the \"example/\" folder in the github repository.").as_str())
+    let contents = fs::read_to_string(&json_path).unwrap_or(default_json());
+
+    parse_config(&contents).unwrap_or_else(|e| {
+        if !REPORTED.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "[{}] The {configuration_part} configuration is not valid ({}), using the default one instead. Run `rsftch --check-config` for details.",
+                "ERROR".red(),
+                describe_error(&json_path, &e)
+            );
+        }
+        parse_config(&default_json()).unwrap()
+    })
 }
 
-pub(crate) fn parse_json_to_vec(custom_config_file: Option<String>) -> Vec<Vec<String>> {
+pub(crate) fn parse_json_to_vec(custom_config_file: Option<String>) -> Vec<Vec<ModuleConfig>> {
     let info = fetch_json(custom_config_file, "info");
 
     info.info
-        .iter()
-        .map(|inner_vec| inner_vec.iter().map(|s| s.to_string()).collect())
+        .into_iter()
+        .map(|inner_vec| {
+            inner_vec
+                .into_iter()
+                .map(|entry| match entry {
+                    ModuleEntry::Name(module) => ModuleConfig {
+                        module,
+                        title: None,
+                        icon: None,
+                        format: None,
+                    },
+                    ModuleEntry::Config(config) => config,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// User-defined modules, keyed by their lowercased name.
+pub(crate) fn get_custom_modules(
+    custom_config_file: Option<String>,
+) -> HashMap<String, CustomModule> {
+    let json_path = fetch_json_path(custom_config_file.clone());
+
+    fetch_json(custom_config_file, "modules")
+        .modules
+        .into_iter()
+        .map(|(name, mut module)| {
+            module.file = module
+                .file
+                .and_then(|file| resolve_config_path(&json_path, &file));
+            (name.to_lowercase(), module)
+        })
         .collect()
 }
 
+/// Resolves a path set in the config: `~/` is the home directory, and relative paths are
+/// relative to the config's directory.
+fn resolve_config_path(json_path: &Path, path: &Path) -> Option<PathBuf> {
+    match path.strip_prefix("~") {
+        Ok(rest) => Some(dirs::home_dir()?.join(rest)),
+        Err(_) => Some(json_path.parent()?.join(path)),
+    }
+}
+
+/// The logo set in the config, `None` when there is none or its file can't be read.
+pub(crate) fn get_logo(custom_config_file: Option<String>) -> Option<String> {
+    let json_path = fetch_json_path(custom_config_file.clone());
+
+    match fetch_json(custom_config_file, "logo").logo? {
+        LogoConfig::Lines(lines) => Some(lines.join("\n")),
+        LogoConfig::Text(text) if text.contains('\n') => Some(text),
+        LogoConfig::Text(path) => {
+            let path = resolve_config_path(&json_path, Path::new(&path))?;
+
+            fs::read_to_string(&path)
+                .map_err(|e| {
+                    eprintln!(
+                        "[{}] Could not read logo {}: {e}",
+                        "WARNING".yellow(),
+                        path.display()
+                    )
+                })
+                .ok()
+        }
+    }
+}
+
+/// A logo for `distro_id` from the user's logo directory, `~/.config/rsftch/logos/<id>.txt`.
+pub(crate) fn get_user_logo(distro_id: &str) -> Option<String> {
+    let path = dirs::config_dir()?
+        .join("rsftch/logos")
+        .join(format!("{distro_id}.txt"));
+
+    fs::read_to_string(path).ok()
+}
+
+pub(crate) fn get_disk_config(custom_config_file: Option<String>) -> DiskConfig {
+    fetch_json(custom_config_file, "disk")
+        .disk
+        .unwrap_or_default()
+}
+
+pub(crate) fn get_cpu_usage_config(custom_config_file: Option<String>) -> CpuUsageConfig {
+    fetch_json(custom_config_file, "cpu_usage")
+        .cpu_usage
+        .unwrap_or_default()
+}
+
+pub(crate) fn get_network_config(custom_config_file: Option<String>) -> NetworkConfig {
+    fetch_json(custom_config_file, "network")
+        .network
+        .unwrap_or_default()
+}
+
+/// The palette for the logo's `${c1}` to `${c6}` placeholders, `None` when not set.
+pub(crate) fn get_logo_colors(custom_config_file: Option<String>) -> Option<Vec<Color>> {
+    fetch_json(custom_config_file, "logo_colors")
+        .logo_colors
+        .map(|colors| {
+            colors
+                .iter()
+                .map(|s| parse_color(s).unwrap_or(Color::White))
+                .collect()
+        })
+}
+
 pub(crate) fn get_colors(custom_config_file: Option<String>, ignore_config: bool) -> Vec<Color> {
     if ignore_config {
         return vec![Color::Red, Color::Green, Color::Blue, Color::Magenta];
@@ -54,15 +346,229 @@ pub(crate) fn get_colors(custom_config_file: Option<String>, ignore_config: bool
 
     info.color
         .iter()
-        .map(|s| match s.to_lowercase().as_str() {
-            "red" => Color::Red,
-            "green" => Color::Green,
-            "yellow" => Color::Yellow,
-            "blue" => Color::Blue,
-            "magenta" | "purple" => Color::Magenta,
-            "cyan" => Color::Cyan,
-            "black" => Color::Black,
-            _ => Color::White,
-        })
+        .map(|s| parse_color(s).unwrap_or(Color::White))
         .collect()
 }
+
+/// Parses a color from the config: a name ("red", "bright_red"), a hex code ("#ff8800" or
+/// "#f80"), "rgb(255, 136, 0)" or a 0-255 palette index. Anything beyond the 16 basic colors
+/// is truecolor, which `colored` degrades to the nearest basic color unless `COLORTERM` says
+/// the terminal supports it.
+pub(crate) fn parse_color(color: &str) -> Option<Color> {
+    let color = color.trim().to_lowercase();
+
+    if let Some(hex) = color.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+
+    if let Some(rgb) = color
+        .strip_prefix("rgb(")
+        .and_then(|rgb| rgb.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = rgb
+            .split(',')
+            .map(|channel| channel.trim().parse().ok())
+            .collect::<Option<_>>()?;
+
+        return match channels[..] {
+            [r, g, b] => Some(Color::TrueColor { r, g, b }),
+            _ => None,
+        };
+    }
+
+    if let Ok(index) = color.parse::<u8>() {
+        return Some(palette_color(index));
+    }
+
+    let (bright, name) = match color
+        .strip_prefix("bright")
+        .or_else(|| color.strip_prefix("light"))
+    {
+        Some(name) => (true, name.trim_start_matches(['_', '-', ' '])),
+        None => (false, color.as_str()),
+    };
+
+    let basic = match name {
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" | "purple" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "black" => Color::Black,
+        "white" => Color::White,
+        "gray" | "grey" if !bright => Color::BrightBlack,
+        _ => return None,
+    };
+
+    Some(match bright {
+        true => brighten(basic),
+        false => basic,
+    })
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+
+    let channel = |i: usize| u8::from_str_radix(expanded.get(i..i + 2)?, 16).ok();
+
+    Some(Color::TrueColor {
+        r: channel(0)?,
+        g: channel(2)?,
+        b: channel(4)?,
+    })
+}
+
+fn brighten(color: Color) -> Color {
+    match color {
+        Color::Black => Color::BrightBlack,
+        Color::Red => Color::BrightRed,
+        Color::Green => Color::BrightGreen,
+        Color::Yellow => Color::BrightYellow,
+        Color::Blue => Color::BrightBlue,
+        Color::Magenta => Color::BrightMagenta,
+        Color::Cyan => Color::BrightCyan,
+        Color::White => Color::BrightWhite,
+        other => other,
+    }
+}
+
+/// Maps an xterm 256-color palette index to a color.
+fn palette_color(index: u8) -> Color {
+    const BASIC: [Color; 8] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+    ];
+
+    match index {
+        0..=7 => BASIC[index as usize],
+        8..=15 => brighten(BASIC[index as usize - 8]),
+        16..=231 => {
+            let level = |value: u8| match value {
+                0 => 0,
+                _ => 55 + value * 40,
+            };
+            let index = index - 16;
+
+            Color::TrueColor {
+                r: level(index / 36),
+                g: level((index / 6) % 6),
+                b: level(index % 6),
+            }
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            Color::TrueColor {
+                r: gray,
+                g: gray,
+                b: gray,
+            }
+        }
+    }
+}
+
+pub(crate) fn get_timeout(custom_config_file: Option<String>, ignore_config: bool) -> u64 {
+    if ignore_config {
+        return DEFAULT_TIMEOUT_MS;
+    }
+
+    fetch_json(custom_config_file, "timeout")
+        .timeout
+        .unwrap_or(DEFAULT_TIMEOUT_MS)
+}
+
+pub(crate) fn get_logo_position(
+    custom_config_file: Option<String>,
+    ignore_config: bool,
+) -> LogoPosition {
+    if ignore_config {
+        return LogoPosition::Top;
+    }
+
+    match fetch_json(custom_config_file, "logo_position").logo_position {
+        Some(position) => LogoPosition::parse(&position).unwrap_or_else(|| {
+            eprintln!(
+                "[{}] Unknown logo position \"{position}\", expected top, left, right or none.",
+                "WARNING".yellow()
+            );
+            LogoPosition::Top
+        }),
+        None => LogoPosition::Top,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORANGE: Color = Color::TrueColor {
+        r: 255,
+        g: 136,
+        b: 0,
+    };
+
+    fn gray(level: u8) -> Color {
+        Color::TrueColor {
+            r: level,
+            g: level,
+            b: level,
+        }
+    }
+
+    #[test]
+    fn parses_hex_and_rgb() {
+        assert_eq!(parse_color("#f80"), Some(ORANGE));
+        assert_eq!(parse_color("#ff8800"), Some(ORANGE));
+        assert_eq!(parse_color("#FF8800"), Some(ORANGE));
+        assert_eq!(parse_color("rgb(255, 136, 0)"), Some(ORANGE));
+        assert_eq!(parse_hex("f80"), Some(ORANGE));
+    }
+
+    #[test]
+    fn parses_palette_indices() {
+        assert_eq!(parse_color("0"), Some(Color::Black));
+        assert_eq!(parse_color("8"), Some(Color::BrightBlack));
+        assert_eq!(palette_color(16), gray(0));
+        assert_eq!(palette_color(231), gray(255));
+        assert_eq!(palette_color(232), gray(8));
+        assert_eq!(palette_color(255), gray(238));
+        assert_eq!(
+            palette_color(208),
+            Color::TrueColor {
+                r: 255,
+                g: 135,
+                b: 0
+            }
+        );
+    }
+
+    #[test]
+    fn parses_bright_and_gray_names() {
+        assert_eq!(parse_color("bright_red"), Some(Color::BrightRed));
+        assert_eq!(parse_color("light-red"), Some(Color::BrightRed));
+        assert_eq!(parse_color("gray"), Some(Color::BrightBlack));
+        assert_eq!(parse_color("purple"), Some(Color::Magenta));
+        assert_eq!(brighten(Color::White), Color::BrightWhite);
+        assert_eq!(brighten(ORANGE), ORANGE);
+    }
+
+    #[test]
+    fn rejects_malformed_colors() {
+        assert_eq!(parse_color("rgb(1,2)"), None);
+        assert_eq!(parse_color("rgb(1,2,300)"), None);
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+        assert_eq!(parse_color("bright_gray"), None);
+        assert_eq!(parse_color("256"), None);
+    }
+}
@@ -0,0 +1,43 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::{fs, path::PathBuf, process::Command};
+
+/// A module declared under "modules" in info.json, its value is either the output of
+/// `command` or the contents of `file`, optionally narrowed down by `regex`. Like the logo,
+/// `file` is relative to the config's directory unless it starts with `~/` or `/`.
+#[derive(Clone, Deserialize)]
+pub(crate) struct CustomModule {
+    pub(crate) title: Option<String>,
+    #[serde(default)]
+    pub(crate) icon: String,
+    pub(crate) command: Option<String>,
+    pub(crate) file: Option<PathBuf>,
+    pub(crate) regex: Option<String>,
+}
+
+impl CustomModule {
+    pub(crate) fn value(&self) -> Option<String> {
+        let output = match (&self.command, &self.file) {
+            (Some(command), _) => Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).to_string())?,
+            (None, Some(file)) => fs::read_to_string(file).ok()?,
+            (None, None) => return None,
+        };
+
+        match &self.regex {
+            Some(pattern) => {
+                let captures = Regex::new(pattern).ok()?.captures(&output)?;
+                captures
+                    .get(1)
+                    .or_else(|| captures.get(0))
+                    .map(|m| m.as_str().trim().to_string())
+            }
+            None => Some(output.trim().to_string()),
+        }
+    }
+}
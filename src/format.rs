@@ -0,0 +1,518 @@
+use rsftch::info::{
+    Battery, BatteryStatus, Cpu, CpuUsage, Desktop, Disk, Display, Gpu, IpAddress, Load, Memory,
+    NetworkInterface, PackageCount, SessionType, Swap, Terminal, Zram,
+};
+use std::net::IpAddr;
+use std::time::Duration;
+
+pub(crate) const NOT_AVAILABLE: &str = "N/A";
+
+/// A module's formatted value, along with the named pieces it was built from so a `format`
+/// in the config can rearrange them, e.g. "{used}/{total}" for memory.
+pub(crate) struct Value {
+    text: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Value {
+    fn new(text: String, fields: Vec<(&'static str, String)>) -> Value {
+        Value { text, fields }
+    }
+
+    pub(crate) fn not_available() -> Value {
+        Value::new(NOT_AVAILABLE.to_string(), Vec::new())
+    }
+
+    /// Fills in `{value}` and the module's own placeholders, or returns the default text
+    /// when there is no format. Modules without data are always "N/A".
+    pub(crate) fn render(&self, format: Option<&str>) -> String {
+        match format {
+            Some(format) if self.text != NOT_AVAILABLE => self.fields.iter().fold(
+                format.replace("{value}", &self.text),
+                |acc, (name, field)| acc.replace(&format!("{{{name}}}"), field),
+            ),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+fn gib(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+}
+
+/// Formats a byte count the way `df -h` does, e.g. "9.5G" or "252G".
+fn human_size(bytes: u64) -> String {
+    let units = ["B", "K", "M", "G", "T", "P"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    match unit > 0 && size < 10.0 {
+        true => format!("{:.1}{}", size, units[unit]),
+        false => format!("{:.0}{}", size, units[unit]),
+    }
+}
+
+pub(crate) fn text(value: Option<String>) -> Value {
+    match value {
+        Some(value) => Value::new(value, Vec::new()),
+        None => Value::not_available(),
+    }
+}
+
+fn temperature(temperature: Option<f64>) -> String {
+    temperature.map_or_else(|| NOT_AVAILABLE.to_string(), |t| format!("{t:.1}°C"))
+}
+
+/// Formats MHz as GHz, e.g. "5.7 GHz".
+fn frequency(mhz: Option<u32>) -> String {
+    mhz.map_or_else(
+        || NOT_AVAILABLE.to_string(),
+        |mhz| format!("{:.1} GHz", mhz as f64 / 1000.0),
+    )
+}
+
+pub(crate) fn cpu(cpu: Option<Cpu>) -> Value {
+    let Some(cpu) = cpu else {
+        return Value::not_available();
+    };
+
+    let temp = temperature(cpu.temperature);
+    Value::new(
+        format!("{} ({temp})", cpu.model),
+        vec![
+            ("model", cpu.model),
+            ("temp", temp),
+            ("cores", cpu.cores.to_string()),
+            ("threads", cpu.threads.to_string()),
+            ("sockets", cpu.sockets.to_string()),
+            ("freq", frequency(cpu.frequency)),
+            ("max_freq", frequency(cpu.max_frequency)),
+        ],
+    )
+}
+
+/// The overall usage, or with `per_core` one row per CPU.
+pub(crate) fn cpu_usage(usage: Option<CpuUsage>, per_core: bool) -> Vec<Value> {
+    let Some(usage) = usage else {
+        return vec![Value::not_available()];
+    };
+
+    let percent = |usage: f64| format!("{usage:.0}");
+    let cores = usage
+        .cores
+        .iter()
+        .map(|&core| format!("{}%", percent(core)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match per_core {
+        true => usage
+            .cores
+            .iter()
+            .enumerate()
+            .map(|(index, &core)| {
+                let name = format!("cpu{index}");
+                let percent = percent(core);
+
+                Value::new(
+                    format!("{name}: {percent}%"),
+                    vec![("name", name), ("percent", percent)],
+                )
+            })
+            .collect(),
+        false => vec![Value::new(
+            format!("{}%", percent(usage.total)),
+            vec![("percent", percent(usage.total)), ("cores", cores)],
+        )],
+    }
+}
+
+pub(crate) fn load(load: Option<Load>) -> Value {
+    let Some(load) = load else {
+        return Value::not_available();
+    };
+
+    let (one, five, fifteen) = (
+        format!("{:.2}", load.one),
+        format!("{:.2}", load.five),
+        format!("{:.2}", load.fifteen),
+    );
+
+    Value::new(
+        format!("{one}, {five}, {fifteen}"),
+        vec![
+            ("one", one),
+            ("five", five),
+            ("fifteen", fifteen),
+            ("running", load.running.to_string()),
+            ("total", load.total.to_string()),
+        ],
+    )
+}
+
+/// One row per GPU with its driver, N/A without any.
+pub(crate) fn gpus(gpus: Vec<Gpu>) -> Vec<Value> {
+    if gpus.is_empty() {
+        return vec![Value::not_available()];
+    }
+
+    gpus.into_iter()
+        .map(|gpu| {
+            let temp = temperature(gpu.temperature);
+            let model = format!("{} {}", gpu.vendor, gpu.name).trim().to_string();
+            let text = match &gpu.driver {
+                Some(driver) => format!("{model} [{driver}] ({temp})"),
+                None => format!("{model} ({temp})"),
+            };
+
+            Value::new(
+                text,
+                vec![
+                    ("name", model),
+                    ("vendor", gpu.vendor),
+                    ("model", gpu.name),
+                    (
+                        "driver",
+                        gpu.driver.unwrap_or_else(|| NOT_AVAILABLE.to_string()),
+                    ),
+                    ("temp", temp),
+                ],
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn memory(memory: Option<Memory>) -> Value {
+    let Some(memory) = memory else {
+        return Value::not_available();
+    };
+
+    let used = format!("{:.2} GiB", gib(memory.used));
+    let total = format!("{:.2} GiB", gib(memory.total));
+    let percent = (memory.used * 100)
+        .checked_div(memory.total)
+        .unwrap_or(0)
+        .to_string();
+
+    Value::new(
+        format!("{used} / {total}"),
+        vec![("used", used), ("total", total), ("percent", percent)],
+    )
+}
+
+/// Like memory, adding how well zram compresses when some of the swap is on zram.
+pub(crate) fn swap(swap: Option<Swap>) -> Value {
+    let Some(swap) = swap else {
+        return Value::not_available();
+    };
+
+    let used = format!("{:.2} GiB", gib(swap.used));
+    let total = format!("{:.2} GiB", gib(swap.total));
+    let percent = (swap.used * 100)
+        .checked_div(swap.total)
+        .unwrap_or(0)
+        .to_string();
+    let zram =
+        swap.areas
+            .iter()
+            .filter_map(|area| area.zram)
+            .fold(Zram::default(), |total, zram| Zram {
+                original: total.original + zram.original,
+                compressed: total.compressed + zram.compressed,
+            });
+    let ratio = zram.ratio().map(|ratio| format!("{ratio:.1}x"));
+    let areas = swap
+        .areas
+        .iter()
+        .map(|area| area.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let text = match (&ratio, swap.total) {
+        (_, 0) => "disabled".to_string(),
+        (Some(ratio), _) => format!("{used} / {total} ({percent}%, zram {ratio})"),
+        (None, _) => format!("{used} / {total} ({percent}%)"),
+    };
+
+    Value::new(
+        text,
+        vec![
+            ("used", used),
+            ("total", total),
+            ("percent", percent),
+            ("ratio", ratio.unwrap_or_else(|| NOT_AVAILABLE.to_string())),
+            ("areas", areas),
+        ],
+    )
+}
+
+/// One row per mounted filesystem.
+pub(crate) fn disks(disks: Vec<Disk>) -> Vec<Value> {
+    disks
+        .into_iter()
+        .map(|disk| {
+            let used = human_size(disk.used);
+            let total = human_size(disk.total);
+            let percent = disk.percent_used().to_string();
+            let text = match disk.fs_type.is_empty() {
+                true => format!("{} {used} / {total} ({percent}%)", disk.mount_point),
+                false => format!(
+                    "{} ({}) {used} / {total} ({percent}%)",
+                    disk.mount_point, disk.fs_type
+                ),
+            };
+
+            Value::new(
+                text,
+                vec![
+                    ("filesystem", disk.filesystem.clone()),
+                    ("device", disk.filesystem),
+                    ("type", disk.fs_type),
+                    ("mount", disk.mount_point),
+                    ("used", used),
+                    ("total", total),
+                    ("percent", percent),
+                ],
+            )
+        })
+        .collect()
+}
+
+/// The count from each source, e.g. "1204 (pacman), 37 (flatpak)", `{count}` being their sum.
+pub(crate) fn packages(packages: Vec<PackageCount>) -> Value {
+    if packages.is_empty() {
+        return Value::not_available();
+    }
+
+    let summed: u32 = packages.iter().map(|pack| pack.count).sum();
+    let managers: Vec<&str> = packages.iter().map(|pack| pack.manager.as_str()).collect();
+    let managers = managers.join(", ");
+    let breakdown = packages
+        .iter()
+        .map(|pack| format!("{} ({})", pack.count, pack.manager))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Value::new(
+        breakdown,
+        vec![("count", summed.to_string()), ("managers", managers)],
+    )
+}
+
+/// One row per connected output, N/A without any.
+pub(crate) fn displays(displays: Vec<Display>) -> Vec<Value> {
+    if displays.is_empty() {
+        return vec![Value::not_available()];
+    }
+
+    displays
+        .into_iter()
+        .map(|display| {
+            let resolution = format!("{}x{}", display.width, display.height);
+
+            Value::new(
+                format!("{resolution} ({})", display.name),
+                vec![
+                    ("name", display.name),
+                    ("resolution", resolution),
+                    ("width", display.width.to_string()),
+                    ("height", display.height.to_string()),
+                ],
+            )
+        })
+        .collect()
+}
+
+/// "DE (WM, session)", leaving out whichever is unknown, e.g. "Sway (wayland)" or "tty".
+pub(crate) fn desktop(desktop: Desktop) -> Value {
+    let session = match desktop.session {
+        SessionType::X11 => "x11",
+        SessionType::Wayland => "wayland",
+        SessionType::Tty => "tty",
+    }
+    .to_string();
+
+    let text = match (&desktop.de, &desktop.wm) {
+        (Some(de), Some(wm)) if de != wm => format!("{de} ({wm}, {session})"),
+        (Some(name), _) | (None, Some(name)) => format!("{name} ({session})"),
+        (None, None) => session.clone(),
+    };
+
+    Value::new(
+        text,
+        vec![
+            (
+                "de",
+                desktop.de.unwrap_or_else(|| NOT_AVAILABLE.to_string()),
+            ),
+            (
+                "wm",
+                desktop.wm.unwrap_or_else(|| NOT_AVAILABLE.to_string()),
+            ),
+            ("session", session),
+        ],
+    )
+}
+
+/// The terminal followed by the multiplexer and SSH, e.g. "kitty (tmux, ssh)".
+pub(crate) fn terminal(terminal: Terminal) -> Value {
+    let Some(name) = terminal.name else {
+        return Value::not_available();
+    };
+
+    let extras: Vec<&str> = terminal
+        .multiplexer
+        .as_deref()
+        .into_iter()
+        .chain(terminal.ssh.then_some("ssh"))
+        .collect();
+    let text = match extras.is_empty() {
+        true => name.clone(),
+        false => format!("{name} ({})", extras.join(", ")),
+    };
+    let multiplexer = terminal
+        .multiplexer
+        .unwrap_or_else(|| NOT_AVAILABLE.to_string());
+
+    Value::new(
+        text,
+        vec![
+            ("name", name),
+            ("multiplexer", multiplexer),
+            ("ssh", terminal.ssh.to_string()),
+        ],
+    )
+}
+
+pub(crate) fn duration(duration: Option<Duration>) -> Value {
+    let Some(duration) = duration else {
+        return Value::not_available();
+    };
+
+    let seconds = duration.as_secs();
+    let mut values = vec![
+        (seconds / (24 * 3600), "days"),
+        ((seconds % (24 * 3600)) / 3600, "hours"),
+        ((seconds % 3600) / 60, "minutes"),
+        (seconds % 60, "seconds"),
+    ];
+    let fields = values
+        .iter()
+        .map(|&(value, unit)| (unit, value.to_string()))
+        .collect();
+
+    values.retain(|&(value, _)| value > 0);
+
+    Value::new(
+        values
+            .iter()
+            .map(|&(value, unit)| format!("{} {}", value, unit))
+            .collect::<Vec<_>>()
+            .join(", "),
+        fields,
+    )
+}
+
+/// One row per battery, named when there is more than one, and none without a battery.
+pub(crate) fn batteries(batteries: Vec<Battery>) -> Vec<Value> {
+    let named = batteries.len() > 1;
+
+    batteries
+        .into_iter()
+        .map(|battery| {
+            let capacity = battery.capacity.to_string();
+            let status = match battery.status {
+                BatteryStatus::Charging => "charging",
+                BatteryStatus::Discharging => "discharging",
+                BatteryStatus::Full => "full",
+                BatteryStatus::NotCharging => "not charging",
+                BatteryStatus::Unknown => "unknown",
+            }
+            .to_string();
+            let time = battery.time_to_empty.map(|time| {
+                let minutes = time.as_secs() / 60;
+                format!("{}h {}m", minutes / 60, minutes % 60)
+            });
+
+            let mut text = match &time {
+                Some(time) => format!("{capacity}% ({status}, {time} left)"),
+                None => format!("{capacity}% ({status})"),
+            };
+            if named {
+                text = format!("{}: {text}", battery.name);
+            }
+
+            Value::new(
+                text,
+                vec![
+                    ("name", battery.name),
+                    ("capacity", capacity),
+                    ("status", status),
+                    ("time", time.unwrap_or_else(|| NOT_AVAILABLE.to_string())),
+                ],
+            )
+        })
+        .collect()
+}
+
+/// One row per interface. IPv6 link-local addresses are left out of the text as every
+/// interface has one, but are still in `{ipv6}`.
+pub(crate) fn network(interfaces: Vec<NetworkInterface>) -> Vec<Value> {
+    interfaces
+        .into_iter()
+        .map(|interface| {
+            let join = |addresses: Vec<String>| match addresses.is_empty() {
+                true => NOT_AVAILABLE.to_string(),
+                false => addresses.join(", "),
+            };
+            let format = |ip: &IpAddress| format!("{}/{}", ip.address, ip.prefix);
+
+            let ipv4 = join(
+                interface
+                    .addresses
+                    .iter()
+                    .filter(|ip| ip.address.is_ipv4())
+                    .map(format)
+                    .collect(),
+            );
+            let ipv6 = join(
+                interface
+                    .addresses
+                    .iter()
+                    .filter(|ip| ip.address.is_ipv6())
+                    .map(format)
+                    .collect(),
+            );
+            let shown = join(
+                interface
+                    .addresses
+                    .iter()
+                    .filter(|ip| match ip.address {
+                        IpAddr::V6(address) => !address.is_unicast_link_local(),
+                        IpAddr::V4(_) => true,
+                    })
+                    .map(format)
+                    .collect(),
+            );
+
+            Value::new(
+                format!("{}: {shown} ({})", interface.name, interface.state),
+                vec![
+                    ("name", interface.name),
+                    ("ipv4", ipv4),
+                    ("ipv6", ipv6),
+                    ("state", interface.state),
+                    (
+                        "mac",
+                        interface.mac.unwrap_or_else(|| NOT_AVAILABLE.to_string()),
+                    ),
+                ],
+            )
+        })
+        .collect()
+}
@@ -4,132 +4,334 @@ use std::{
     env,
     fs::{self, read_to_string, File},
     io::{BufRead, BufReader, Read},
-    path::Path,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
-    sync::{Arc, Mutex},
+    str::FromStr,
     time::Duration,
 };
 
-#[cfg(target_os = "linux")]
-use {once_cell::sync::Lazy, regex::Regex};
+use crate::sysroot::{has_sysroot, sys_path};
+
+/// Memory usage in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Memory {
+    pub used: u64,
+    pub total: u64,
+}
+
+/// Swap usage in bytes, with the areas it is spread over.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Swap {
+    pub used: u64,
+    pub total: u64,
+    pub areas: Vec<SwapArea>,
+}
+
+/// A swap partition, file or zram device from `/proc/swaps`, sizes in bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapArea {
+    pub name: String,
+    pub used: u64,
+    pub total: u64,
+    pub zram: Option<Zram>,
+}
+
+/// How much data a zram device holds before and after compression, in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Zram {
+    pub original: u64,
+    pub compressed: u64,
+}
+
+impl Zram {
+    /// How many times smaller the data is once compressed, `None` while the device is empty.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.compressed > 0).then(|| self.original as f64 / self.compressed as f64)
+    }
+}
+
+/// Processor model name, without the frequency suffix, its temperature in °C, its topology
+/// and its current and maximum frequency in MHz. The current frequency is that of the fastest
+/// core.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cpu {
+    pub model: String,
+    pub temperature: Option<f64>,
+    pub cores: u32,
+    pub threads: u32,
+    pub sockets: u32,
+    pub frequency: Option<u32>,
+    pub max_frequency: Option<u32>,
+}
+
+/// The load averages over 1, 5 and 15 minutes, and how many of the scheduling entities
+/// (processes and threads) are running out of how many there are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Load {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+    pub running: u32,
+    pub total: u32,
+}
+
+/// How busy the processor was, in percent, overall and for each logical CPU.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuUsage {
+    pub total: f64,
+    pub cores: Vec<f64>,
+}
+
+/// A graphics card, the kernel driver bound to it and its temperature in °C.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gpu {
+    pub vendor: String,
+    pub name: String,
+    pub driver: Option<String>,
+    pub temperature: Option<f64>,
+}
+
+/// A mounted filesystem, sizes in bytes. `filesystem` is the mounted device, e.g.
+/// "/dev/nvme0n1p2", and `fs_type` its type, e.g. "ext4", when known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disk {
+    pub filesystem: String,
+    pub fs_type: String,
+    pub mount_point: String,
+    pub used: u64,
+    pub available: u64,
+    pub total: u64,
+}
+
+impl Disk {
+    /// Share of the space usable by unprivileged users that is taken, rounded up like `df`.
+    pub fn percent_used(&self) -> u64 {
+        match self.used + self.available {
+            0 => 0,
+            usable => (self.used * 100).div_ceil(usable),
+        }
+    }
+}
+
+/// Installed package count for a single package manager.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackageCount {
+    pub manager: String,
+    pub count: u32,
+}
+
+/// What a battery is doing, as reported by the kernel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging,
+    Unknown,
+}
+
+/// A system battery, its charge in percent and, while discharging, how long it should last.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Battery {
+    pub name: String,
+    pub capacity: u8,
+    pub status: BatteryStatus,
+    pub time_to_empty: Option<Duration>,
+}
+
+/// An address assigned to a network interface, with the length of its network prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IpAddress {
+    pub address: IpAddr,
+    pub prefix: u8,
+}
+
+/// A network interface and its addresses. `state` is the kernel's operstate, e.g. "up", "down"
+/// or "unknown", and `bridge` marks bridges and the veth pairs plugged into them, such as
+/// docker0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub state: String,
+    pub mac: Option<String>,
+    pub loopback: bool,
+    pub bridge: bool,
+    pub addresses: Vec<IpAddress>,
+}
 
-pub(crate) fn help() {
-    println!(
-        r#"Usage: rsftch [-h / --help / --usage] [-v / --version] [-o / --override <distro name>] [-m / --margin <margin>] [--ignore-config] [--config <absolute path to config>]
-        
-      -h, --help, --usage         Bring up this menu.
-      -v, --version               Print version number.
-      -o, --override              Overrides distribution, affects ASCII and "distro" info. Running without an argument prints all possible options.
-      -m, --margin                Add margin to the info sections, default 1. E.g. `rsftch --info distro` would output: "EndeavourOS".
-          --config                Specify another info config file to be used.
-          --ignore-config         Ignores configuration and uses the example one.
+/// A connected display output and its mode, the preferred one when read from the kernel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Display {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+}
 
-    Configuration file is located at: ~/.config/rsftch/info.json"#
-    );
+/// How the graphical session, if any, is displayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionType {
+    X11,
+    Wayland,
+    Tty,
 }
 
-pub(crate) fn whoami() -> String {
-    let output = Command::new("whoami")
+/// The desktop environment and the window manager or compositor of the user's session.
+/// Window managers used on their own, such as sway, have no desktop environment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Desktop {
+    pub de: Option<String>,
+    pub wm: Option<String>,
+    pub session: SessionType,
+}
+
+/// The terminal emulator rsftch runs in, the multiplexer in between, e.g. "tmux", and whether
+/// it runs over SSH, where the emulator is on the other end and only `$TERM` is known.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Terminal {
+    pub name: Option<String>,
+    pub multiplexer: Option<String>,
+    pub ssh: bool,
+}
+
+pub fn whoami() -> Option<String> {
+    Command::new("whoami")
         .output()
-        .expect("`whoami` failed, are you on a Unix-like operating system?");
-    String::from_utf8_lossy(&output.stdout).trim().to_string()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|user| !user.is_empty())
 }
 
-pub(crate) fn timezone() -> String {
-    let timezone_path = Path::new("/etc/timezone");
+pub fn timezone() -> Option<String> {
+    let timezone_path = sys_path("/etc/timezone");
     if timezone_path.exists() {
-        if let Ok(timezone) = fs::read_to_string(timezone_path) {
-            return timezone.trim().to_string();
+        if let Ok(timezone) = fs::read_to_string(&timezone_path) {
+            return Some(timezone.trim().to_string());
         }
     }
 
-    let localtime_path = Path::new("/etc/localtime");
-    if localtime_path.exists() {
-        if let Ok(symlink_target) = fs::read_link(localtime_path) {
+    let localtime_path = sys_path("/etc/localtime");
+    if localtime_path.is_symlink() {
+        if let Ok(symlink_target) = fs::read_link(&localtime_path) {
             if let Some(target_str) = symlink_target.to_str() {
                 if target_str.contains("/zoneinfo/") {
                     if let Some(tz) = target_str.split("/zoneinfo/").last() {
-                        return tz.to_string();
+                        return Some(tz.to_string());
                     }
                 }
             }
         }
     }
 
-    String::new()
+    None
 }
 
-pub(crate) fn cpu_temp() -> String {
-    #[cfg(target_os = "linux")]
-    {
-        static REGEX: Lazy<Regex> =
-            Lazy::new(|| Regex::new(r"Package id 0:\s+\+(\d+\.\d+)°C").unwrap());
-
-        let output = Command::new("sensors").output().unwrap();
-        let output_str = String::from_utf8_lossy(&output.stdout);
+/// hwmon drivers reporting the processor's temperature, with the sensor labels to prefer in
+/// order. Drivers without labels, such as the ARM SoC ones, use their first sensor.
+#[cfg(target_os = "linux")]
+const CPU_HWMON: &[(&str, &[&str])] = &[
+    ("coretemp", &["Package id 0"]),
+    ("k10temp", &["Tdie", "Tctl"]),
+    ("zenpower", &["Tdie", "Tctl"]),
+    ("cpu_thermal", &[]),
+    ("cpu-thermal", &[]),
+    ("soc_thermal", &[]),
+    ("cpu0_thermal", &[]),
+    ("scpi_sensors", &[]),
+];
+
+/// Thermal zone types that belong to the processor, tried when no hwmon driver matched.
+#[cfg(target_os = "linux")]
+const CPU_THERMAL_ZONES: &[&str] = &[
+    "x86_pkg_temp",
+    "cpu-thermal",
+    "cpu_thermal",
+    "cpu0-thermal",
+    "soc-thermal",
+    "soc_thermal",
+];
+
+/// Reads a sysfs temperature, given in millidegrees Celsius.
+#[cfg(target_os = "linux")]
+fn read_millidegrees(path: &Path) -> Option<f64> {
+    read_sys_value::<f64>(path).map(|millidegrees| millidegrees / 1000.0)
+}
 
-        REGEX
-            .captures(&output_str)
-            .map_or_else(|| "(N/A)".to_string(), |caps| format!("({}°C)", &caps[1]))
+/// The reading of `chip`'s sensor labelled `label`, or its first sensor without labels.
+#[cfg(target_os = "linux")]
+fn hwmon_temp(chip: &Path, labels: &[&str]) -> Option<f64> {
+    if labels.is_empty() {
+        return read_millidegrees(&chip.join("temp1_input"));
     }
 
-    #[cfg(target_os = "netbsd")]
-    {
-        Command::new("envstat")
-            .output()
-            .ok()
-            .and_then(|output| String::from_utf8(output.stdout).ok())
-            .and_then(|output_str| {
-                output_str
-                    .lines()
-                    .skip_while(|line| *line != "[acpitz0]")
-                    .nth(1)
-                    .and_then(|line| line.split(':').nth(1))
-                    .map(|s| s.split_whitespace().next().unwrap_or(""))
-                    .and_then(|temp_str| temp_str.parse::<f64>().ok())
-                    .map(|temp| format!("({:.1}°C)", temp))
-            })
-            .unwrap_or(String::from("(N/A)"))
-    }
+    let sensors: Vec<(String, String)> = fs::read_dir(chip)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file = entry.file_name().to_string_lossy().into_owned();
+            let sensor = file.strip_suffix("_label")?.to_string();
+            Some((read_sys_value(&entry.path())?, sensor))
+        })
+        .collect();
+
+    labels.iter().find_map(|label| {
+        sensors
+            .iter()
+            .find(|(sensor_label, _)| sensor_label == label)
+            .and_then(|(_, sensor)| read_millidegrees(&chip.join(format!("{sensor}_input"))))
+    })
+}
+
+/// Sysfs entries in `class`, e.g. "/sys/class/hwmon", in name order.
+#[cfg(target_os = "linux")]
+fn sys_class(class: &str) -> Vec<PathBuf> {
+    sorted_dir(&sys_path(class))
 }
 
-fn gpu_temp() -> String {
+/// The entries of `dir` in name order, none when it can't be read.
+fn sorted_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    paths.sort();
+    paths
+}
+
+pub fn cpu_temp() -> Option<f64> {
     #[cfg(target_os = "linux")]
     {
-        Command::new("nvidia-smi")
-            .arg("--query-gpu=temperature.gpu")
-            .arg("--format=csv,noheader")
-            .output()
-            .ok()
-            .and_then(|output| String::from_utf8(output.stdout).ok())
-            .and_then(|temp_str| {
-                temp_str
-                    .lines()
-                    .next()
-                    .and_then(|s| s.trim().parse::<f64>().ok())
+        let chips: Vec<(String, PathBuf)> = sys_class("/sys/class/hwmon")
+            .into_iter()
+            .filter_map(|chip| Some((read_sys_value(&chip.join("name"))?, chip)))
+            .collect();
+
+        CPU_HWMON
+            .iter()
+            .find_map(|(driver, labels)| {
+                chips
+                    .iter()
+                    .filter(|(name, _)| name == driver)
+                    .find_map(|(_, chip)| hwmon_temp(chip, labels))
             })
-            .map(|temp| format!("({:.1}°C)", temp))
-            .unwrap_or_else(|| {
-                Command::new("sensors")
-                    .output()
-                    .ok()
-                    .and_then(|output| String::from_utf8(output.stdout).ok())
-                    .and_then(|output_str| {
-                        output_str
-                            .lines()
-                            .find(|line| {
-                                line.contains("temp1:")
-                                    || line.contains("edge:")
-                                    || line.contains("junction:")
-                                    || line.contains("mem:")
-                            })
-                            .and_then(|line| line.split_whitespace().nth(1))
-                            .and_then(|temp_str| {
-                                temp_str.trim_end_matches("°C").parse::<f64>().ok()
-                            })
-                            .map(|temp| format!("({:.1}°C)", temp))
-                    })
-                    .unwrap_or(String::from("(N/A)"))
+            .or_else(|| {
+                let zones: Vec<(String, PathBuf)> = sys_class("/sys/class/thermal")
+                    .into_iter()
+                    .filter_map(|zone| Some((read_sys_value(&zone.join("type"))?, zone)))
+                    .collect();
+
+                CPU_THERMAL_ZONES.iter().find_map(|zone_type| {
+                    zones
+                        .iter()
+                        .filter(|(name, _)| name == zone_type)
+                        .find_map(|(_, zone)| read_millidegrees(&zone.join("temp")))
+                })
             })
     }
 
@@ -142,112 +344,340 @@ fn gpu_temp() -> String {
             .and_then(|output_str| {
                 output_str
                     .lines()
-                    .skip_while(|line| *line != "[acpitz2]")
+                    .skip_while(|line| *line != "[acpitz0]")
                     .nth(1)
                     .and_then(|line| line.split(':').nth(1))
                     .map(|s| s.split_whitespace().next().unwrap_or(""))
                     .and_then(|temp_str| temp_str.parse::<f64>().ok())
-                    .map(|temp| format!("({:.1}°C)", temp))
             })
-            .unwrap_or(String::from("(N/A)"))
     }
 }
 
-fn extract_gpu_name(line: &str) -> String {
-    let parts: Vec<&str> = line.split('[').collect();
-    if parts.len() >= 3 {
-        return parts[2].split(']').next().unwrap_or("Unknown GPU").trim().to_string();
+#[cfg(target_os = "netbsd")]
+fn gpu_temp() -> Option<f64> {
+    Command::new("envstat")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|output_str| {
+            output_str
+                .lines()
+                .skip_while(|line| *line != "[acpitz2]")
+                .nth(1)
+                .and_then(|line| line.split(':').nth(1))
+                .map(|s| s.split_whitespace().next().unwrap_or(""))
+                .and_then(|temp_str| temp_str.parse::<f64>().ok())
+        })
+}
+
+/// Where distributions install the PCI ID database.
+#[cfg(target_os = "linux")]
+const PCI_IDS: &[&str] = &[
+    "/usr/share/hwdata/pci.ids",
+    "/usr/share/misc/pci.ids",
+    "/usr/share/pci.ids",
+    "/var/lib/pciutils/pci.ids",
+];
+
+/// Looks up the vendor and device names of `vendor`:`device` (lowercase hex) in pci.ids.
+#[cfg(target_os = "linux")]
+fn pci_names(vendor: &str, device: &str) -> (Option<String>, Option<String>) {
+    let Some(ids) = PCI_IDS
+        .iter()
+        .find_map(|path| fs::read_to_string(sys_path(path)).ok())
+    else {
+        return (None, None);
+    };
+
+    let mut vendor_name = None;
+
+    for line in ids.lines() {
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
+        if !line.starts_with('\t') {
+            // Vendors are sorted, and the device classes follow them.
+            if vendor_name.is_some() || line.starts_with("C ") {
+                break;
+            }
+            vendor_name = line
+                .strip_prefix(vendor)
+                .map(|name| name.trim().to_string());
+        } else if vendor_name.is_some() && !line.starts_with("\t\t") {
+            if let Some(name) = line[1..].strip_prefix(device) {
+                return (vendor_name, Some(name.trim().to_string()));
+            }
+        }
     }
-    String::from("Unknown GPU")
+
+    (vendor_name, None)
 }
 
-pub(crate) fn gpu_info() -> String {
+/// The temperature of the GPU at `device`, from its hwmon sensor, or `nvidia-smi` for the
+/// proprietary NVIDIA driver which has none.
+#[cfg(target_os = "linux")]
+fn gpu_temp(device: &Path, driver: Option<&str>) -> Option<f64> {
+    sorted_dir(&device.join("hwmon"))
+        .iter()
+        .find_map(|hwmon| read_millidegrees(&hwmon.join("temp1_input")))
+        .or_else(|| {
+            (driver == Some("nvidia")).then_some(())?;
+            let address = device.file_name()?.to_string_lossy().into_owned();
+
+            Command::new("nvidia-smi")
+                .arg("--query-gpu=temperature.gpu")
+                .arg("--format=csv,noheader")
+                .arg(format!("--id={address}"))
+                .output()
+                .ok()
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .and_then(|temp| temp.lines().next()?.trim().parse().ok())
+        })
+}
+
+/// Every display controller on the PCI bus, in bus order, so usually the integrated GPU
+/// comes first. Names come from pci.ids when it is installed, with the marketing name in
+/// brackets preferred, e.g. "GeForce RTX 3060 Mobile / Max-Q" over "GA106M".
+pub fn gpus() -> Vec<Gpu> {
     #[cfg(target_os = "linux")]
     {
-        let output = Command::new("lspci")
-            .arg("-nnk")
-            .output()
-            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
-            .unwrap_or_else(|_| format!("N/A {}", gpu_temp()));
-
-        let reader = BufReader::new(output.as_bytes());
-
-        for line in reader.lines().flatten() {
-            if line.contains("NVIDIA") {
-                let prefix = "NVIDIA";
-                let gpu_name = extract_gpu_name(&line);
-                return format!("{} {} {}", prefix, gpu_name, gpu_temp());
-            } else if line.contains("AMD") {
-                let prefix = if line.contains("Radeon") {
-                    "AMD"
-                } else {
-                    "AMD Radeon"
+        sys_class("/sys/bus/pci/devices")
+            .into_iter()
+            .filter(|device| {
+                read_sys_value::<String>(&device.join("class"))
+                    .is_some_and(|class| class.starts_with("0x03"))
+            })
+            .filter_map(|device| {
+                let id = |file: &str| {
+                    read_sys_value::<String>(&device.join(file))
+                        .map(|id| id.trim_start_matches("0x").to_lowercase())
                 };
-                let gpu_name = extract_gpu_name(&line);
-                return format!("{} {} {}", prefix, gpu_name, gpu_temp());
-            } else if line.contains("Intel") {
-                if line.contains("VGA compatible controller") || line.contains("3D controller") {
-                    let prefix = "Intel Integrated";
-                    let gpu_name = extract_gpu_name(&line);
-                    return format!("{} {} {}", prefix, gpu_name, gpu_temp());
-                }
-            }
-        }
-
-        format!("N/A {}", gpu_temp())
+                let vendor_id = id("vendor")?;
+                let device_id = id("device")?;
+                let (vendor_name, device_name) = pci_names(&vendor_id, &device_id);
+
+                let vendor = match vendor_id.as_str() {
+                    "10de" => "NVIDIA".to_string(),
+                    "1002" => "AMD".to_string(),
+                    "8086" => "Intel".to_string(),
+                    _ => vendor_name.unwrap_or_else(|| format!("Vendor {vendor_id}")),
+                };
+                let name = match device_name {
+                    Some(name) => match (name.rfind('['), name.rfind(']')) {
+                        (Some(open), Some(close)) if open < close => {
+                            name[open + 1..close].to_string()
+                        }
+                        _ => name,
+                    },
+                    None => format!("Device {device_id}"),
+                };
+                let driver = fs::read_link(device.join("driver"))
+                    .ok()
+                    .and_then(|driver| Some(driver.file_name()?.to_string_lossy().into_owned()));
+
+                Some(Gpu {
+                    temperature: gpu_temp(&device, driver.as_deref()),
+                    vendor,
+                    name,
+                    driver,
+                })
+            })
+            .collect()
     }
     #[cfg(target_os = "netbsd")]
     {
-        let formatted_str = Command::new("pcictl")
+        Command::new("pcictl")
             .args(&["pci0", "list"])
             .output()
             .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
-            .unwrap_or_else(|_| format!("N/A {}", gpu_temp()))
+            .unwrap_or_default()
             .lines()
-            .find(|&l| l.contains("VGA display"))
-            .and_then(|l| l.rsplitn(2, ':').next())
-            .map(|name| {
-                name.trim()
+            .filter(|&l| l.contains("VGA display"))
+            .filter_map(|l| l.rsplitn(2, ':').next())
+            .map(|name| Gpu {
+                vendor: String::new(),
+                name: name
+                    .trim()
                     .split_at(name.find('(').unwrap_or(0))
                     .0
                     .trim()
-                    .to_string()
+                    .to_string(),
+                driver: None,
+                temperature: gpu_temp(),
             })
-            .unwrap_or_else(|| format!("N/A {}", gpu_info()));
+            .collect()
+    }
+}
 
-        format!("{} {}", formatted_str, gpu_temp())
+/// Filesystems without storage of their own, never shown as disks.
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "rpc_pipefs",
+    "securityfs",
+    "sysfs",
+    "tracefs",
+];
+
+/// Undoes the octal escapes `/proc/self/mounts` uses for spaces and the like, e.g. "\040".
+fn unescape_mount_field(field: &str) -> String {
+    let mut unescaped = String::new();
+    let mut rest = field;
+
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+        let code = rest.get(index + 1..index + 4);
+
+        match code.and_then(|code| u8::from_str_radix(code, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
     }
+
+    unescaped.push_str(rest);
+    unescaped
 }
 
-pub(crate) fn disk_usage() -> String {
-    let output_str = match Command::new("df").arg("-h").output() {
+/// Sizes of the filesystem mounted at `path`, as (used, available, total) in bytes.
+// The statvfs field types differ between platforms, so some of these casts are no-ops.
+#[allow(clippy::unnecessary_cast)]
+fn statvfs(path: &Path) -> Option<(u64, u64, u64)> {
+    use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+
+    // SAFETY: `path` is NUL-terminated and `stat` is only read once statvfs has filled it in.
+    let stat = match unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } {
+        0 => unsafe { stat.assume_init() },
+        _ => return None,
+    };
+
+    let fragment = stat.f_frsize as u64;
+    let total = stat.f_blocks as u64 * fragment;
+    let free = stat.f_bfree as u64 * fragment;
+    let available = stat.f_bavail as u64 * fragment;
+
+    Some((total.saturating_sub(free), available, total))
+}
+
+/// The filesystems mounted at `mount_points`, in that order. Mount points which are not
+/// mounted on their own (e.g. "/home" on the root filesystem) or hold a pseudo filesystem are
+/// left out. Reads `/proc/self/mounts`, falling back to `df` where there is none.
+pub fn disk_usage(mount_points: &[String]) -> Vec<Disk> {
+    let Ok(mounts) = fs::read_to_string(sys_path("/proc/self/mounts")) else {
+        return df_usage(mount_points);
+    };
+
+    // (device, type, mount point), the last entry for a mount point being the visible one.
+    let mounts: Vec<(String, String, String)> = mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = unescape_mount_field(fields.next()?);
+            let mount_point = unescape_mount_field(fields.next()?);
+            let fs_type = fields.next()?.to_string();
+            Some((device, fs_type, mount_point))
+        })
+        .filter(|(_, fs_type, _)| !PSEUDO_FILESYSTEMS.contains(&fs_type.as_str()))
+        .collect();
+
+    mount_points
+        .iter()
+        .filter_map(|mount_point| {
+            let (device, fs_type, _) = mounts.iter().rev().find(|(_, _, m)| m == mount_point)?;
+            let (used, available, total) = statvfs(&sys_path(mount_point))?;
+
+            (total > 0).then(|| Disk {
+                filesystem: device.clone(),
+                fs_type: fs_type.clone(),
+                mount_point: mount_point.clone(),
+                used,
+                available,
+                total,
+            })
+        })
+        .collect()
+}
+
+fn df_usage(mount_points: &[String]) -> Vec<Disk> {
+    let output_str = match Command::new("df").arg("-k").output() {
         Ok(output) if output.status.success() => {
             String::from_utf8(output.stdout).unwrap_or_default()
         }
-        _ => return String::from("N/A"),
+        _ => return Vec::new(),
     };
 
-    let line = output_str.lines().find(|line| line.starts_with('/'));
+    let disks: Vec<Disk> = output_str
+        .lines()
+        .filter(|line| line.starts_with('/'))
+        .filter_map(|line| {
+            let parts: Vec<_> = line.split_whitespace().collect();
+            if parts.len() < 6 {
+                return None;
+            }
 
-    if let Some(line) = line {
-        let parts: Vec<_> = line.split_whitespace().collect();
-        if parts.len() >= 5 {
-            let filesystem = parts[0];
-            let used = parts[2];
-            let size = parts[1];
-            let capacity = parts[4];
-            return format!("({}) {} / {} ({})", filesystem, used, size, capacity);
-        }
-    }
+            Some(Disk {
+                filesystem: parts[0].to_string(),
+                fs_type: String::new(),
+                mount_point: parts[5].to_string(),
+                used: parts[2].parse::<u64>().ok()? * 1024,
+                available: parts[3].parse::<u64>().ok()? * 1024,
+                total: parts[1].parse::<u64>().ok()? * 1024,
+            })
+        })
+        .collect();
 
-    String::new()
+    mount_points
+        .iter()
+        .filter_map(|mount_point| {
+            disks
+                .iter()
+                .find(|disk| disk.mount_point == *mount_point)
+                .cloned()
+        })
+        .collect()
 }
 
-pub(crate) fn cpu_info() -> String {
-    let cpuinfo_file = match read_to_string("/proc/cpuinfo") {
-        Ok(content) => content,
-        Err(_) => return format!("N/A {}", cpu_temp()),
-    };
+/// The highest value of `file` in the cpufreq directory of any processor, in MHz.
+fn cpufreq(file: &str) -> Option<u32> {
+    let cpus = fs::read_dir(sys_path("/sys/devices/system/cpu")).ok()?;
+
+    cpus.filter_map(Result::ok)
+        .filter(|cpu| {
+            cpu.file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .filter_map(|cpu| read_sys_value::<u64>(&cpu.path().join("cpufreq").join(file)))
+        .max()
+        .map(|khz| (khz / 1000) as u32)
+}
+
+pub fn cpu_info() -> Option<Cpu> {
+    let cpuinfo_file = read_to_string(sys_path("/proc/cpuinfo")).ok()?;
 
     let keys: HashSet<&str> = [
         "model name",
@@ -261,59 +691,60 @@ pub(crate) fn cpu_info() -> String {
     .cloned()
     .collect();
 
+    let mut model = None;
+    let mut threads = 0;
+    let mut cores = HashSet::new();
+    let mut sockets = HashSet::new();
+    let mut physical_id = "0";
+    let mut megahertz: Option<u32> = None;
+
     for line in cpuinfo_file.lines() {
         if let Some(pos) = line.find(": ") {
-            let key = &line[..pos];
-            let value = &line[pos + 2..].trim();
-
-            if keys.contains(&key.trim()) {
-                return format!("{}{}", value.split('@').next().unwrap_or(value), cpu_temp());
+            let key = line[..pos].trim();
+            let value = line[pos + 2..].trim();
+
+            match key {
+                "processor" => threads += 1,
+                "physical id" => {
+                    physical_id = value;
+                    sockets.insert(value);
+                }
+                "core id" => {
+                    cores.insert((physical_id, value));
+                }
+                "cpu MHz" => {
+                    megahertz = value
+                        .parse::<f64>()
+                        .ok()
+                        .map(|mhz| mhz as u32)
+                        .max(megahertz);
+                }
+                _ if model.is_none() && keys.contains(key) => {
+                    model = Some(value.split('@').next().unwrap_or(value).trim().to_string());
+                }
+                _ => {}
             }
         }
     }
 
-    format!("N/A {}", cpu_temp())
-}
-
-fn package_managers() -> Vec<String> {
-    let possible_managers = vec![
-        "xbps-query",
-        "dnf",
-        "rpm",
-        "apt",
-        "pacman",
-        "emerge",
-        "yum",
-        "zypper",
-        "apk",
-        "pkg_info",
-        "pkg",
-    ];
-
-    possible_managers
-        .par_iter()
-        .filter_map(|manager| {
-            let version_command = match *manager {
-                "pkg_info" => "-V",
-                "emerge" => "--help",
-                _ => "--version",
-            };
-
-            if Command::new(manager)
-                .arg(version_command)
-                .output()
-                .ok()
-                .map_or(false, |result| result.status.success())
-            {
-                Some(manager.to_string())
-            } else {
-                None
-            }
-        })
-        .collect()
+    // ARM and friends list neither cores nor sockets, where every thread is a core.
+    let threads = threads.max(1);
+
+    Some(Cpu {
+        model: model?,
+        temperature: cpu_temp(),
+        cores: match cores.len() {
+            0 => threads,
+            cores => cores as u32,
+        },
+        threads,
+        sockets: sockets.len().max(1) as u32,
+        frequency: cpufreq("scaling_cur_freq").or(megahertz),
+        max_frequency: cpufreq("cpuinfo_max_freq"),
+    })
 }
 
-fn count_packages(command: &str, args: &[&str]) -> Option<i16> {
+fn count_packages(command: &str, args: &[&str]) -> Option<u32> {
     let mut cmd = Command::new(command)
         .args(args)
         .stdout(Stdio::piped())
@@ -322,125 +753,402 @@ fn count_packages(command: &str, args: &[&str]) -> Option<i16> {
 
     let output = cmd.stdout.take()?;
     let reader = BufReader::new(output);
-    let line_count = reader.lines().count() as i16;
+    let line_count = reader.lines().count() as u32;
 
     let _ = cmd.wait().ok()?;
     Some(line_count)
 }
 
-pub(crate) fn packages() -> String {
-    let managers = package_managers();
-    let packs_numbers = Arc::new(Mutex::new(Vec::new()));
-
-    managers.par_iter().for_each(|manager| {
-        let count = match manager.as_str() {
-            "xbps-query" => count_packages(manager, &["-l"]),
-            "dnf" | "yum" => count_packages(manager, &["list", "installed"]),
-            "rpm" => count_packages(manager, &["-qa", "--last"]),
-            "apt" => count_packages("dpkg", &["--list"]),
-            "pacman" => count_packages(manager, &["-Q"]),
-            "zypper" => count_packages(manager, &["se"]),
-            "apk" => count_packages(manager, &["list", "--installed"]),
-            "pkg_info" => count_packages("ls", &["/usr/pkg/pkgdb/"]).map(|x| x - 1),
-            "pkg" => count_packages(manager, &["info"]),
-            "emerge" => {
-                if os_pretty_name(None, "ID")
-                    .unwrap_or_default()
-                    .to_ascii_lowercase()
-                    .contains("funtoo")
-                {
-                    count_packages("find", &["/var/db/pkg/", "-name", "PF"])
-                } else {
-                    count_packages(manager, &["-I"])
-                }
-            }
-            _ => None,
+/// Counts the packages in a package manager's database, `None` when the database is not there.
+type PackageCounter = fn() -> Option<u32>;
+
+/// Every package manager whose database rsftch can read.
+const PACKAGE_DATABASES: &[(&str, PackageCounter)] = &[
+    ("pacman", pacman_packages),
+    ("dpkg", dpkg_packages),
+    ("xbps", xbps_packages),
+    ("apk", apk_packages),
+    ("rpm", rpm_packages),
+    ("emerge", portage_packages),
+    ("pkgsrc", pkgsrc_packages),
+    ("pkg", pkgng_packages),
+    ("flatpak", flatpak_system_packages),
+    ("flatpak-user", flatpak_user_packages),
+    ("snap", snap_packages),
+    ("nix-default", nix_default_packages),
+    ("nix-user", nix_user_packages),
+    ("brew", brew_packages),
+    ("cargo", cargo_packages),
+];
+
+/// A path in the user's home directory, e.g. ".cargo", under the sysroot like any other.
+fn home_path(path: &str) -> Option<PathBuf> {
+    let home = env::var("HOME").ok()?;
+    Some(sys_path(&format!("{}/{path}", home.trim_end_matches('/'))))
+}
+
+/// Counts the directories in `dir` holding a `marker` file, `None` when `dir` is missing.
+fn count_marked_dirs(dir: &Path, marker: &str) -> Option<u32> {
+    let count = fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join(marker).is_file())
+        .count();
+
+    Some(count as u32)
+}
+
+/// Counts the directories in `dir` whose `link` points at a directory, like Flatpak's "active"
+/// deployment or Snap's "current" revision, `None` when `dir` is missing.
+fn count_linked_dirs(dir: &Path, link: &str) -> Option<u32> {
+    let count = fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join(link).is_dir())
+        .count();
+
+    Some(count as u32)
+}
+
+/// One directory with a "desc" per package, next to the ALPM_DB_VERSION file.
+fn pacman_packages() -> Option<u32> {
+    count_marked_dirs(&sys_path("/var/lib/pacman/local"), "desc")
+}
+
+/// Stanzas of the status file whose package is installed, rather than removed with its
+/// config files left behind or half-installed.
+fn dpkg_packages() -> Option<u32> {
+    let status = fs::read_to_string(sys_path("/var/lib/dpkg/status")).ok()?;
+    let count = status
+        .lines()
+        .filter_map(|line| line.strip_prefix("Status:"))
+        .filter(|status| status.split_whitespace().nth(2) == Some("installed"))
+        .count();
+
+    Some(count as u32)
+}
+
+/// Entries of the pkgdb property list whose state is installed.
+fn xbps_packages() -> Option<u32> {
+    let pkgdb = sorted_dir(&sys_path("/var/db/xbps"))
+        .into_iter()
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("pkgdb-") && name.ends_with(".plist"))
+        })?;
+    let plist = fs::read_to_string(pkgdb).ok()?;
+    let lines: Vec<&str> = plist.lines().map(str::trim).collect();
+    let count = lines
+        .windows(2)
+        .filter(|pair| pair == &["<key>state</key>", "<string>installed</string>"])
+        .count();
+
+    Some(count as u32)
+}
+
+/// One "P:" (package name) line per package.
+fn apk_packages() -> Option<u32> {
+    let installed = fs::read_to_string(sys_path("/lib/apk/db/installed")).ok()?;
+    let count = installed
+        .lines()
+        .filter(|line| line.starts_with("P:"))
+        .count();
+
+    Some(count as u32)
+}
+
+/// The rpm database is SQLite, Berkeley DB or ndb depending on the distribution and its age,
+/// none of which can be read without a library, so this asks rpm itself, and only when the
+/// database is there. Dnf, yum and zypper all keep their packages in it.
+fn rpm_packages() -> Option<u32> {
+    let database = ["/var/lib/rpm", "/usr/lib/sysimage/rpm"]
+        .iter()
+        .map(|path| sys_path(path))
+        .find(|path| path.is_dir())?;
+
+    count_packages("rpm", &["--dbpath", database.to_str()?, "-qa"])
+}
+
+/// Portage's `/var/db/pkg/<category>/<package>`, each with a "PF" (full package name) file.
+fn portage_packages() -> Option<u32> {
+    let categories = sorted_dir(&sys_path("/var/db/pkg"));
+    let count = categories
+        .iter()
+        .filter_map(|category| count_marked_dirs(category, "PF"))
+        .sum::<u32>();
+
+    (count > 0).then_some(count)
+}
+
+/// pkgsrc's `/usr/pkg/pkgdb`, or `/var/db/pkg` on recent NetBSD, one directory with a
+/// "+CONTENTS" per package.
+fn pkgsrc_packages() -> Option<u32> {
+    ["/usr/pkg/pkgdb", "/var/db/pkg"]
+        .iter()
+        .filter_map(|path| count_marked_dirs(&sys_path(path), "+CONTENTS"))
+        .find(|&count| count > 0)
+}
+
+/// FreeBSD's pkg keeps a SQLite database, so like rpm this asks pkg itself.
+fn pkgng_packages() -> Option<u32> {
+    if !sys_path("/var/db/pkg/local.sqlite").is_file() {
+        return None;
+    }
+
+    count_packages("pkg", &["info"])
+}
+
+/// Apps and runtimes of a Flatpak installation, one `<kind>/<id>/<arch>/<branch>` each with
+/// an "active" deployment, so every installed branch of a runtime counts.
+fn flatpak_packages(installation: &Path) -> Option<u32> {
+    if !installation.is_dir() {
+        return None;
+    }
+
+    let count = ["app", "runtime"]
+        .iter()
+        .flat_map(|kind| sorted_dir(&installation.join(kind)))
+        .flat_map(|id| sorted_dir(&id))
+        .filter_map(|arch| count_linked_dirs(&arch, "active"))
+        .sum();
+
+    Some(count)
+}
+
+fn flatpak_system_packages() -> Option<u32> {
+    flatpak_packages(&sys_path("/var/lib/flatpak"))
+}
+
+fn flatpak_user_packages() -> Option<u32> {
+    flatpak_packages(&home_path(".local/share/flatpak")?)
+}
+
+/// Every mounted snap has a "current" link to its revision, which `/snap/bin` does not.
+fn snap_packages() -> Option<u32> {
+    ["/snap", "/var/lib/snapd/snap"]
+        .iter()
+        .filter_map(|path| count_linked_dirs(&sys_path(path), "current"))
+        .find(|&count| count > 0)
+}
+
+/// The packages installed in a Nix profile, from `manifest.json` for `nix profile` or
+/// `manifest.nix` for `nix-env`, where each one is a derivation.
+fn nix_profile_packages(profile: &Path) -> Option<u32> {
+    if let Ok(manifest) = fs::read_to_string(profile.join("manifest.json")) {
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+        let count = match &manifest["elements"] {
+            serde_json::Value::Array(elements) => elements.len(),
+            serde_json::Value::Object(elements) => elements.len(),
+            _ => 0,
         };
 
-        if let Some(count) = count {
-            packs_numbers.lock().unwrap().push(count);
+        return Some(count as u32);
+    }
+
+    let manifest = fs::read_to_string(profile.join("manifest.nix")).ok()?;
+    Some(manifest.matches("type = \"derivation\";").count() as u32)
+}
+
+fn nix_default_packages() -> Option<u32> {
+    nix_profile_packages(&sys_path("/nix/var/nix/profiles/default"))
+}
+
+/// The user's profile, `~/.nix-profile` or where newer Nix keeps it.
+fn nix_user_packages() -> Option<u32> {
+    [".nix-profile", ".local/state/nix/profile"]
+        .iter()
+        .filter_map(|path| nix_profile_packages(&home_path(path)?))
+        .next()
+}
+
+/// Homebrew on Linux keeps a directory per formula in its Cellar, shared or per user.
+fn brew_packages() -> Option<u32> {
+    let shared = sys_path("/home/linuxbrew/.linuxbrew/Cellar");
+    let user = home_path(".linuxbrew/Cellar");
+
+    [Some(shared), user]
+        .into_iter()
+        .flatten()
+        .filter(|cellar| cellar.is_dir())
+        .map(|cellar| sorted_dir(&cellar).len() as u32)
+        .next()
+}
+
+/// Crates installed with `cargo install`, listed in `.crates2.json` or, by older cargo, in
+/// the "v1" table of `.crates.toml`.
+fn cargo_packages() -> Option<u32> {
+    let cargo_home = match env::var("CARGO_HOME") {
+        Ok(path) => sys_path(&path),
+        Err(_) => home_path(".cargo")?,
+    };
+
+    if let Ok(crates) = fs::read_to_string(cargo_home.join(".crates2.json")) {
+        let crates: serde_json::Value = serde_json::from_str(&crates).ok()?;
+        return Some(crates["installs"].as_object()?.len() as u32);
+    }
+
+    let crates = fs::read_to_string(cargo_home.join(".crates.toml")).ok()?;
+    let count = crates
+        .lines()
+        .skip_while(|line| line.trim() != "[v1]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter(|line| line.contains(" = ["))
+        .count();
+
+    Some(count as u32)
+}
+
+/// Installed package counts for every package manager with a database on the system, read
+/// straight from the databases, followed by Flatpak, Snap, Nix, Homebrew and Cargo. Only rpm
+/// and FreeBSD's pkg, whose formats need a library, are asked through their command.
+pub fn packages() -> Vec<PackageCount> {
+    PACKAGE_DATABASES
+        .par_iter()
+        .filter_map(|(manager, count)| {
+            let count = count().filter(|&count| count > 0)?;
+
+            Some(PackageCount {
+                manager: manager.to_string(),
+                count,
+            })
+        })
+        .collect()
+}
+
+/// Connected outputs from the kernel's DRM connectors, e.g. `/sys/class/drm/card1-eDP-1`,
+/// with their preferred mode, the first one the kernel lists. This works the same on Wayland,
+/// X11 and in a TTY. Without any, such as with drivers that don't expose their connectors,
+/// the current modes are asked from `wlr-randr` on Wayland or `xrandr` on X11, except under a
+/// sysroot as those describe the live system.
+pub fn res() -> Vec<Display> {
+    let displays = drm_displays();
+    if !displays.is_empty() || has_sysroot() {
+        return displays;
+    }
+
+    match (env::var_os("WAYLAND_DISPLAY"), env::var_os("DISPLAY")) {
+        (Some(_), _) => wlr_randr_displays(),
+        (None, Some(_)) => xrandr_displays(),
+        (None, None) => Vec::new(),
+    }
+}
+
+fn drm_displays() -> Vec<Display> {
+    sorted_dir(&sys_path("/sys/class/drm"))
+        .iter()
+        .filter_map(|connector| {
+            let name = connector.file_name()?.to_str()?;
+            let (card, name) = name.split_once('-')?;
+            if !card.starts_with("card") {
+                return None;
+            }
+
+            let status = fs::read_to_string(connector.join("status")).ok()?;
+            if status.trim() != "connected" {
+                return None;
+            }
+
+            let modes = fs::read_to_string(connector.join("modes")).ok()?;
+            let (width, height) = modes.lines().next()?.split_once('x')?;
+
+            Some(Display {
+                name: name.to_string(),
+                width: width.parse().ok()?,
+                // Interlaced modes end in "i", e.g. "1920x1080i".
+                height: height.trim_end_matches('i').parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Parses the outputs `wlr-randr` lists, each followed by its modes, of which the one marked
+/// current, e.g. "    2560x1600 px, 60.002998 Hz (preferred, current)".
+fn wlr_randr_displays() -> Vec<Display> {
+    let Ok(output) = Command::new("wlr-randr").output() else {
+        return Vec::new();
+    };
+
+    let mut displays = Vec::new();
+    let mut name = None;
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if !line.starts_with(char::is_whitespace) {
+            name = line.split_whitespace().next().map(str::to_string);
+            continue;
         }
-    });
 
-    let summed: i16 = packs_numbers.lock().unwrap().par_iter().sum();
+        if !line.contains("current") {
+            continue;
+        }
 
-    match managers.is_empty() {
-        false => format!("{} ({})", summed, managers.join(", ")),
-        true => String::from("N/A"),
+        let mode = line.split_whitespace().next().and_then(|mode| {
+            let (width, height) = mode.split_once('x')?;
+            Some((width.parse().ok()?, height.parse().ok()?))
+        });
+
+        if let (Some(name), Some((width, height))) = (name.take(), mode) {
+            displays.push(Display {
+                name,
+                width,
+                height,
+            });
+        }
     }
+
+    displays
 }
 
-pub(crate) fn res() -> String {
+fn xrandr_displays() -> Vec<Display> {
     let output = match Command::new("xrandr").arg("--query").output() {
         Ok(out) => out,
-        Err(_) => return String::from("N/A"),
+        Err(_) => return Vec::new(),
     };
 
     String::from_utf8_lossy(&output.stdout)
         .lines()
         .filter_map(|line| {
-            if let Some(index) = line.find(" connected") {
-                let line = &line[index + 1..];
-                if let Some(resolution) = line.split_whitespace().find(|s| s.contains('x')) {
-                    Some(resolution.split('+').next().unwrap_or("").to_string())
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
+            let index = line.find(" connected")?;
+            let resolution = line[index + 1..]
+                .split_whitespace()
+                .find(|s| s.contains('x'))?
+                .split('+')
+                .next()?;
+            let (width, height) = resolution.split_once('x')?;
+
+            Some(Display {
+                name: line[..index].to_string(),
+                width: width.parse().ok()?,
+                height: height.parse().ok()?,
+            })
         })
-        .collect::<Vec<String>>()
-        .join(", ")
+        .collect()
 }
 
-pub(crate) fn uptime() -> String {
+pub fn uptime() -> Option<Duration> {
     let mut line = String::new();
 
-    File::open("/proc/uptime")
-        .map_err(|_| "NA".to_string())
-        .and_then(|file| {
-            BufReader::new(file)
-                .read_line(&mut line)
-                .map_err(|_| "N/A".to_string())
-        })
-        .ok();
+    File::open(sys_path("/proc/uptime"))
+        .ok()?
+        .read_to_string(&mut line)
+        .ok()?;
 
-    let uptime_secs: f64 = line
-        .split_whitespace()
+    line.split_whitespace()
         .next()
         .and_then(|val| val.parse().ok())
-        .unwrap_or_default();
-
-    format_duration(Duration::from_secs_f64(uptime_secs)).to_string()
-}
-
-fn format_duration(duration: Duration) -> String {
-    let seconds = duration.as_secs();
-    let mut values = vec![
-        (seconds / (24 * 3600), "days"),
-        ((seconds % (24 * 3600)) / 3600, "hours"),
-        ((seconds % 3600) / 60, "minutes"),
-        (seconds % 60, "seconds"),
-    ];
-
-    values.retain(|&(value, _)| value > 0);
-
-    values
-        .iter()
-        .map(|&(value, unit)| format!("{} {}", value, unit))
-        .collect::<Vec<_>>()
-        .join(", ")
+        .map(Duration::from_secs_f64)
 }
 
 fn search_file(custom_paths: Vec<&'static str>, search_variable: &str) -> Option<String> {
     for path in custom_paths.iter() {
-        if let Ok(content) = fs::read_to_string(path) {
+        if let Ok(content) = fs::read_to_string(sys_path(path)) {
             for line in content.lines() {
-                if line.starts_with(search_variable) {
-                    if let Some(name) = line.split('=').nth(1) {
-                        return Some(name.trim_matches('"').to_string());
-                    }
+                if let Some(name) = line
+                    .strip_prefix(search_variable)
+                    .and_then(|rest| rest.strip_prefix('='))
+                {
+                    return Some(name.trim_matches('"').to_string());
                 }
             }
         }
@@ -448,41 +1156,149 @@ fn search_file(custom_paths: Vec<&'static str>, search_variable: &str) -> Option
     None
 }
 
-pub(crate) fn os_pretty_name(ascii_override: Option<String>, identifier: &str) -> Option<String> {
-    if ascii_override.is_some() {
-        return ascii_override;
-    }
-
+/// Looks up `identifier` (e.g. "NAME" or "ID") in os-release, falling back to lsb-release.
+pub fn os_release(identifier: &str) -> Option<String> {
     search_file(vec!["/etc/os-release", "/etc/lsb-release"], identifier)
 }
 
-pub(crate) fn wm() -> String {
-    if env::var("DISPLAY").is_err() {
-        return String::new();
+/// Window managers and compositors by process name, which the kernel cuts to 15 characters.
+const WINDOW_MANAGERS: &[(&str, &str)] = &[
+    ("sway", "Sway"),
+    ("Hyprland", "Hyprland"),
+    ("river", "river"),
+    ("niri", "niri"),
+    ("labwc", "labwc"),
+    ("wayfire", "Wayfire"),
+    ("weston", "Weston"),
+    ("dwl", "dwl"),
+    ("kwin_wayland", "KWin"),
+    ("kwin_x11", "KWin"),
+    ("gnome-shell", "Mutter"),
+    ("cinnamon", "Muffin"),
+    ("marco", "Marco"),
+    ("xfwm4", "Xfwm4"),
+    ("budgie-wm", "Budgie WM"),
+    ("cosmic-comp", "cosmic-comp"),
+    ("i3", "i3"),
+    ("bspwm", "bspwm"),
+    ("dwm", "dwm"),
+    ("awesome", "awesome"),
+    ("openbox", "Openbox"),
+    ("herbstluftwm", "herbstluftwm"),
+    ("qtile", "Qtile"),
+    ("fluxbox", "Fluxbox"),
+    ("icewm", "IceWM"),
+    ("spectrwm", "spectrwm"),
+    ("leftwm", "LeftWM"),
+    ("enlightenment", "Enlightenment"),
+    ("xmonad-x86_64-l", "xmonad"),
+];
+
+/// Desktop environments by the process name of their session or shell.
+const DESKTOP_ENVIRONMENTS: &[(&str, &str)] = &[
+    ("gnome-shell", "GNOME"),
+    ("plasmashell", "KDE Plasma"),
+    ("xfce4-session", "Xfce"),
+    ("cinnamon-sessio", "Cinnamon"),
+    ("mate-session", "MATE"),
+    ("lxqt-session", "LXQt"),
+    ("lxsession", "LXDE"),
+    ("budgie-panel", "Budgie"),
+    ("cosmic-session", "COSMIC"),
+];
+
+/// The name of every running process, from `/proc/<pid>/comm`.
+fn process_names() -> Vec<String> {
+    sorted_dir(&sys_path("/proc"))
+        .iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|pid| pid.to_str())
+                .is_some_and(|pid| pid.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .filter_map(|path| fs::read_to_string(path.join("comm")).ok())
+        .map(|comm| comm.trim_end().to_string())
+        .collect()
+}
+
+/// Picks the desktop out of a colon separated list such as "ubuntu:GNOME" or "X-Cinnamon",
+/// preferring a known one, e.g. "KDE" for KDE Plasma.
+fn desktop_name(desktops: &str) -> Option<String> {
+    let desktops: Vec<&str> = desktops
+        .split(':')
+        .map(|desktop| desktop.trim_start_matches("X-"))
+        .filter(|desktop| !desktop.is_empty())
+        .collect();
+
+    desktops
+        .iter()
+        .find_map(|desktop| {
+            DESKTOP_ENVIRONMENTS.iter().find_map(|(_, name)| {
+                let short = name.split_whitespace().next()?;
+                (desktop.eq_ignore_ascii_case(name) || desktop.eq_ignore_ascii_case(short))
+                    .then(|| name.to_string())
+            })
+        })
+        .or_else(|| desktops.first().map(|desktop| desktop.to_string()))
+}
+
+fn session_type() -> SessionType {
+    match env::var("XDG_SESSION_TYPE").as_deref() {
+        Ok("wayland") => return SessionType::Wayland,
+        Ok("x11") => return SessionType::X11,
+        _ => {}
     }
 
-    for env_var in &[
-        "XDG_SESSION_DESKTOP",
-        "XDG_CURRENT_DESKTOP",
-        "DESKTOP_SESSION",
-    ] {
-        if let Ok(de) = env::var(env_var) {
-            return de;
-        }
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        SessionType::Wayland
+    } else if env::var_os("DISPLAY").is_some() {
+        SessionType::X11
+    } else {
+        SessionType::Tty
     }
+}
 
-    let path = format!("{}/.xinitrc", env::var("HOME").unwrap_or_default());
-    if let Ok(mut file) = File::open(&path) {
-        let mut buf = String::new();
-        if file.read_to_string(&mut buf).is_ok() {
-            if let Some(last_line) = buf.lines().last() {
-                let last_word = last_line.split(' ').last().unwrap_or("");
-                return last_word.to_string();
-            }
-        }
+/// Finds the window manager among the running processes, and the desktop environment from
+/// `XDG_CURRENT_DESKTOP` and its relatives or, without those, the running processes too. The
+/// session type comes from `XDG_SESSION_TYPE`, `WAYLAND_DISPLAY` and `DISPLAY`. Over SSH the
+/// running desktop is someone else's, so only the session type is given.
+pub fn desktop() -> Desktop {
+    let session = session_type();
+    if env::var_os("SSH_CONNECTION").is_some() || env::var_os("SSH_TTY").is_some() {
+        return Desktop {
+            de: None,
+            wm: None,
+            session,
+        };
     }
 
-    String::from("N/A")
+    let processes = process_names();
+    let find = |known: &[(&str, &'static str)]| {
+        known
+            .iter()
+            .find(|(process, _)| processes.iter().any(|name| name == process))
+            .map(|(_, name)| name.to_string())
+    };
+
+    let wm = find(WINDOW_MANAGERS);
+    let de = [
+        "XDG_CURRENT_DESKTOP",
+        "XDG_SESSION_DESKTOP",
+        "DESKTOP_SESSION",
+    ]
+    .iter()
+    .filter_map(|var| env::var(var).ok())
+    .find_map(|desktop| desktop_name(&desktop))
+    .or_else(|| find(DESKTOP_ENVIRONMENTS))
+    // Window managers used on their own set these to their own name, e.g. "sway".
+    .filter(|de| {
+        DESKTOP_ENVIRONMENTS.iter().any(|(_, name)| name == de)
+            || !WINDOW_MANAGERS
+                .iter()
+                .any(|(_, name)| de.eq_ignore_ascii_case(name))
+    });
+
+    Desktop { de, wm, session }
 }
 
 fn parse_memory_value(line: &str) -> u64 {
@@ -493,69 +1309,632 @@ fn parse_memory_value(line: &str) -> u64 {
         .unwrap_or(0)
 }
 
-pub(crate) fn mem() -> String {
-    let kb_to_gb = |kilobytes: u64| kilobytes as f64 / (1024.0 * 1024.0);
+pub fn mem() -> Option<Memory> {
+    let file = File::open(sys_path("/proc/meminfo")).ok()?;
+    let reader = BufReader::new(file);
+    let mut mem_total: u64 = 0;
+    let mut mem_available: u64 = 0;
+
+    for line in reader.lines().map_while(Result::ok) {
+        if line.starts_with("MemTotal:") {
+            mem_total = parse_memory_value(&line);
+        } else if line.starts_with("MemAvailable:")
+            || (cfg!(target_os = "netbsd") && line.starts_with("MemFree:"))
+        {
+            mem_available = parse_memory_value(&line);
+        }
+    }
+
+    Some(Memory {
+        used: mem_total.saturating_sub(mem_available) * 1024,
+        total: mem_total * 1024,
+    })
+}
 
-    if let Ok(file) = File::open("/proc/meminfo") {
-        let reader = BufReader::new(file);
-        let mut mem_total: u64 = 0;
+/// Reads `/proc/loadavg`, e.g. "0.52 0.61 0.70 2/1532 84211".
+pub fn load_average() -> Option<Load> {
+    let content = fs::read_to_string(sys_path("/proc/loadavg")).ok()?;
+    let mut fields = content.split_whitespace();
+    let mut average = || fields.next()?.parse().ok();
+    let (one, five, fifteen) = (average()?, average()?, average()?);
+    let (running, total) = fields.next()?.split_once('/')?;
+
+    Some(Load {
+        one,
+        five,
+        fifteen,
+        running: running.parse().ok()?,
+        total: total.parse().ok()?,
+    })
+}
 
-        #[cfg(target_os = "linux")]
-        {
-            let mut mem_available: u64 = 0;
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    if line.starts_with("MemTotal:") {
-                        mem_total = parse_memory_value(&line);
-                    } else if line.starts_with("MemAvailable:") {
-                        mem_available = parse_memory_value(&line);
-                    }
-                }
+/// The busy and total time of the whole processor followed by each CPU, in clock ticks, from
+/// `/proc/stat`. Time waiting on IO counts as idle, and guest time is already part of user.
+fn cpu_times() -> Option<Vec<(u64, u64)>> {
+    let content = fs::read_to_string(sys_path("/proc/stat")).ok()?;
+    let times: Vec<(u64, u64)> = content
+        .lines()
+        .filter(|line| line.starts_with("cpu"))
+        .map(|line| {
+            let ticks: Vec<u64> = line
+                .split_whitespace()
+                .skip(1)
+                .take(8)
+                .map(|tick| tick.parse().unwrap_or(0))
+                .collect();
+            let total: u64 = ticks.iter().sum();
+            let idle = ticks.iter().skip(3).take(2).sum::<u64>();
+
+            (total - idle, total)
+        })
+        .collect();
+
+    (!times.is_empty()).then_some(times)
+}
+
+/// Samples `/proc/stat` twice, `interval` apart, and works out how busy the processor was in
+/// between. When no time passed for the kernel, such as with an interval of zero, it is the
+/// usage since boot instead.
+pub fn cpu_usage(interval: Duration) -> Option<CpuUsage> {
+    let before = cpu_times()?;
+    std::thread::sleep(interval);
+    let after = cpu_times()?;
+
+    let percent = |(busy, total): (u64, u64)| match total {
+        0 => 0.0,
+        total => busy as f64 * 100.0 / total as f64,
+    };
+    let usage: Vec<f64> = after
+        .iter()
+        .zip(before.iter().chain(std::iter::repeat(&(0, 0))))
+        .map(|(&(busy, total), &(busy_before, total_before))| {
+            match total.saturating_sub(total_before) {
+                0 => percent((busy, total)),
+                elapsed => percent((busy.saturating_sub(busy_before), elapsed)),
             }
+        })
+        .collect();
 
-            let used = mem_total - mem_available;
-            return format!("{:.2} GiB / {:.2} GiB", kb_to_gb(used), kb_to_gb(mem_total));
-        }
+    Some(CpuUsage {
+        total: usage[0],
+        cores: usage[1..].to_vec(),
+    })
+}
 
-        #[cfg(target_os = "netbsd")]
-        {
-            let mut mem_free: u64 = 0;
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    if line.starts_with("MemTotal:") {
-                        mem_total = parse_memory_value(&line);
-                    } else if line.starts_with("MemAvailable:") || line.starts_with("MemFree:") {
-                        mem_free = parse_memory_value(&line);
+fn read_sys_value<T: FromStr>(path: &Path) -> Option<T> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Estimates the time left from the remaining energy (µWh) and power draw (µW), or failing
+/// those, the remaining charge (µAh) and current (µA).
+fn time_to_empty(path: &Path) -> Option<Duration> {
+    let hours = |now: &str, rate: &str| -> Option<f64> {
+        let now: f64 = read_sys_value(&path.join(now))?;
+        let rate: f64 = read_sys_value(&path.join(rate))?;
+        (rate > 0.0).then(|| now / rate)
+    };
+
+    hours("energy_now", "power_now")
+        .or_else(|| hours("charge_now", "current_now"))
+        .map(|hours| Duration::from_secs_f64(hours * 3600.0))
+}
+
+/// Every system battery in `/sys/class/power_supply`, sorted by name. Batteries of
+/// peripherals such as mice are left out, and machines without one get an empty list.
+pub fn batteries() -> Vec<Battery> {
+    let Ok(entries) = fs::read_dir(sys_path("/sys/class/power_supply")) else {
+        return Vec::new();
+    };
+
+    let mut batteries: Vec<Battery> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            read_sys_value::<String>(&path.join("type")).as_deref() == Some("Battery")
+                && read_sys_value::<String>(&path.join("scope")).as_deref() != Some("Device")
+        })
+        .filter_map(|path| {
+            let capacity: u8 = read_sys_value(&path.join("capacity"))?;
+            let status = match read_sys_value::<String>(&path.join("status")).as_deref() {
+                Some("Charging") => BatteryStatus::Charging,
+                Some("Discharging") => BatteryStatus::Discharging,
+                Some("Full") => BatteryStatus::Full,
+                Some("Not charging") => BatteryStatus::NotCharging,
+                _ => BatteryStatus::Unknown,
+            };
+            let time_to_empty = match status {
+                BatteryStatus::Discharging => time_to_empty(&path),
+                _ => None,
+            };
+
+            Some(Battery {
+                name: path.file_name()?.to_string_lossy().into_owned(),
+                capacity: capacity.min(100),
+                status,
+                time_to_empty,
+            })
+        })
+        .collect();
+
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+    batteries
+}
+
+/// Every address of every interface from `getifaddrs`, with the prefix of its own netmask.
+fn interface_addresses() -> Option<Vec<(String, IpAddress)>> {
+    use std::{ffi::CStr, ptr};
+
+    let mut head: *mut libc::ifaddrs = ptr::null_mut();
+    // SAFETY: on success `head` points to a list which stays valid until freeifaddrs.
+    if unsafe { libc::getifaddrs(&mut head) } != 0 {
+        return None;
+    }
+
+    let mut addresses = Vec::new();
+    let mut current = head;
+
+    while !current.is_null() {
+        // SAFETY: `current` is a non-null node of the list getifaddrs returned, whose name is a
+        // NUL-terminated string and whose address and netmask, when set, match their family.
+        let (entry, address) = unsafe {
+            let entry = &*current;
+            current = entry.ifa_next;
+
+            if entry.ifa_addr.is_null() {
+                continue;
+            }
+
+            let netmask = entry.ifa_netmask;
+            let address = match (*entry.ifa_addr).sa_family as i32 {
+                libc::AF_INET => {
+                    let address = &*(entry.ifa_addr as *const libc::sockaddr_in);
+                    let prefix = match netmask.is_null() {
+                        true => 32,
+                        false => (*(netmask as *const libc::sockaddr_in))
+                            .sin_addr
+                            .s_addr
+                            .count_ones(),
+                    };
+
+                    IpAddress {
+                        address: Ipv4Addr::from(address.sin_addr.s_addr.to_ne_bytes()).into(),
+                        prefix: prefix as u8,
                     }
                 }
-            }
+                libc::AF_INET6 => {
+                    let address = &*(entry.ifa_addr as *const libc::sockaddr_in6);
+                    let prefix = match netmask.is_null() {
+                        true => 128,
+                        false => (*(netmask as *const libc::sockaddr_in6))
+                            .sin6_addr
+                            .s6_addr
+                            .iter()
+                            .map(|byte| byte.count_ones())
+                            .sum(),
+                    };
+
+                    IpAddress {
+                        address: Ipv6Addr::from(address.sin6_addr.s6_addr).into(),
+                        prefix: prefix as u8,
+                    }
+                }
+                _ => continue,
+            };
+
+            (entry, address)
+        };
+
+        // SAFETY: see above, the name lives as long as the list.
+        let name = unsafe { CStr::from_ptr(entry.ifa_name) };
+        addresses.push((name.to_string_lossy().into_owned(), address));
+    }
+
+    // SAFETY: `head` came from getifaddrs and nothing borrowed from the list outlives this.
+    unsafe { libc::freeifaddrs(head) };
 
-            let used = mem_total - mem_free;
-            return format!("{:.2} GiB / {:.2} GiB", kb_to_gb(used), kb_to_gb(mem_total));
+    Some(addresses)
+}
+
+/// Local IPv4 addresses from the kernel's routing trie, the "/32 host LOCAL" leaves.
+fn local_ipv4_addresses() -> Vec<Ipv4Addr> {
+    let Ok(trie) = fs::read_to_string(sys_path("/proc/net/fib_trie")) else {
+        return Vec::new();
+    };
+
+    let mut addresses = Vec::new();
+    let mut last = None;
+
+    for line in trie.lines().map(str::trim) {
+        if let Some(address) = line.strip_prefix("|-- ") {
+            last = address.parse().ok();
+        } else if line.starts_with("/32 host LOCAL") {
+            if let Some(address) = last.filter(|address| !addresses.contains(address)) {
+                addresses.push(address);
+            }
         }
     }
 
-    String::from("N/A")
+    addresses
+}
+
+/// Routes to directly connected networks as (interface, network, mask).
+fn ipv4_routes() -> Vec<(String, Ipv4Addr, Ipv4Addr)> {
+    let parse = |hex: &str| {
+        u32::from_str_radix(hex, 16)
+            .ok()
+            .map(|value| Ipv4Addr::from(value.to_ne_bytes()))
+    };
+
+    fs::read_to_string(sys_path("/proc/net/route"))
+        .unwrap_or_default()
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let mask = parse(fields.get(7)?)?;
+            (!mask.is_unspecified()).then_some((
+                fields[0].to_string(),
+                parse(fields.get(1)?)?,
+                mask,
+            ))
+        })
+        .collect()
 }
 
-pub(crate) fn uname(arg: &str, ascii_override: Option<String>) -> String {
-    if ascii_override.is_some() {
-        return ascii_override.unwrap();
+/// Pairs each local IPv4 address with the interface of the most specific connected route it
+/// falls in, loopback addresses going to "lo".
+fn ipv4_addresses() -> Vec<(String, IpAddress)> {
+    let routes = ipv4_routes();
+
+    local_ipv4_addresses()
+        .into_iter()
+        .filter_map(|address| {
+            if address.is_loopback() {
+                return Some((
+                    "lo".to_string(),
+                    IpAddress {
+                        address: address.into(),
+                        prefix: 8,
+                    },
+                ));
+            }
+
+            routes
+                .iter()
+                .filter(|(_, network, mask)| {
+                    u32::from(address) & u32::from(*mask) == u32::from(*network)
+                })
+                .max_by_key(|(_, _, mask)| u32::from(*mask).count_ones())
+                .map(|(interface, _, mask)| {
+                    let prefix = u32::from(*mask).count_ones() as u8;
+                    (
+                        interface.clone(),
+                        IpAddress {
+                            address: address.into(),
+                            prefix,
+                        },
+                    )
+                })
+        })
+        .collect()
+}
+
+/// IPv6 addresses from `/proc/net/if_inet6`, which lists them with their interface.
+fn ipv6_addresses() -> Vec<(String, IpAddress)> {
+    fs::read_to_string(sys_path("/proc/net/if_inet6"))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let address = u128::from_str_radix(fields.first()?, 16).ok()?;
+            let prefix = u8::from_str_radix(fields.get(2)?, 16).ok()?;
+
+            Some((
+                fields.get(5)?.to_string(),
+                IpAddress {
+                    address: Ipv6Addr::from(address).into(),
+                    prefix,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Every network interface in `/sys/class/net`, sorted by name, with its IPv4 addresses
+/// followed by its IPv6 ones. The addresses come from `getifaddrs`, or under a sysroot from
+/// procfs, where IPv4 addresses are matched to interfaces through the main routing table.
+pub fn network_interfaces() -> Vec<NetworkInterface> {
+    let Ok(entries) = fs::read_dir(sys_path("/sys/class/net")) else {
+        return Vec::new();
+    };
+
+    let mut addresses = match has_sysroot() {
+        true => None,
+        false => interface_addresses(),
     }
+    .unwrap_or_else(|| {
+        ipv4_addresses()
+            .into_iter()
+            .chain(ipv6_addresses())
+            .collect()
+    });
+    addresses.sort_by_key(|(_, ip)| ip.address.is_ipv6());
+
+    let mut interfaces: Vec<NetworkInterface> = entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            NetworkInterface {
+                state: read_sys_value(&path.join("operstate"))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                mac: read_sys_value(&path.join("address"))
+                    .filter(|mac: &String| mac != "00:00:00:00:00:00"),
+                // ARPHRD_LOOPBACK
+                loopback: read_sys_value::<u16>(&path.join("type")) == Some(772),
+                bridge: path.join("bridge").is_dir() || name.starts_with("veth"),
+                addresses: addresses
+                    .iter()
+                    .filter(|(interface, _)| *interface == name)
+                    .map(|&(_, address)| address)
+                    .collect(),
+                name,
+            }
+        })
+        .collect();
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+/// Reads `/sys/block/<device>/mm_stat`, whose first two fields are the original and the
+/// compressed size of the stored data.
+fn zram(device: &str) -> Option<Zram> {
+    let stat = fs::read_to_string(sys_path(&format!("/sys/block/{device}/mm_stat"))).ok()?;
+    let mut fields = stat.split_whitespace().map(|field| field.parse::<u64>());
+
+    Some(Zram {
+        original: fields.next()?.ok()?,
+        compressed: fields.next()?.ok()?,
+    })
+}
 
-    let output = Command::new("uname")
+pub fn swap() -> Option<Swap> {
+    let file = File::open(sys_path("/proc/meminfo")).ok()?;
+    let reader = BufReader::new(file);
+    let mut swap_total: u64 = 0;
+    let mut swap_free: u64 = 0;
+
+    for line in reader.lines().map_while(Result::ok) {
+        if line.starts_with("SwapTotal:") {
+            swap_total = parse_memory_value(&line);
+        } else if line.starts_with("SwapFree:") {
+            swap_free = parse_memory_value(&line);
+        }
+    }
+
+    let areas = fs::read_to_string(sys_path("/proc/swaps"))
+        .unwrap_or_default()
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let name = fields.first()?.to_string();
+            let zram = name
+                .strip_prefix("/dev/")
+                .filter(|device| device.starts_with("zram"))
+                .and_then(zram);
+
+            Some(SwapArea {
+                total: fields.get(2)?.parse::<u64>().ok()? * 1024,
+                used: fields.get(3)?.parse::<u64>().ok()? * 1024,
+                name,
+                zram,
+            })
+        })
+        .collect();
+
+    Some(Swap {
+        used: swap_total.saturating_sub(swap_free) * 1024,
+        total: swap_total * 1024,
+        areas,
+    })
+}
+
+/// Runs `uname` with a single flag, e.g. "-n" for the hostname or "-r" for the kernel release.
+pub fn uname(arg: &str) -> Option<String> {
+    Command::new("uname")
         .arg(arg)
         .output()
-        .expect(format!("`uname` failed {arg}, this should not happen.").as_str());
-    String::from_utf8_lossy(&output.stdout).trim().to_string()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn shell_name() -> Option<String> {
+    env::var("SHELL")
+        .ok()
+        .and_then(|shell| shell.split('/').next_back().map(str::to_string))
+}
+
+/// Terminal emulators by process name, which the kernel cuts to 15 characters.
+const TERMINALS: &[(&str, &str)] = &[
+    ("kitty", "kitty"),
+    ("alacritty", "Alacritty"),
+    ("foot", "foot"),
+    ("footclient", "foot"),
+    ("wezterm-gui", "WezTerm"),
+    ("ghostty", "Ghostty"),
+    ("gnome-terminal-", "GNOME Terminal"),
+    ("kgx", "Console"),
+    ("ptyxis-agent", "Ptyxis"),
+    ("konsole", "Konsole"),
+    ("xfce4-terminal", "Xfce Terminal"),
+    ("mate-terminal", "MATE Terminal"),
+    ("lxterminal", "LXTerminal"),
+    ("qterminal", "QTerminal"),
+    ("tilix", "Tilix"),
+    ("terminator", "Terminator"),
+    ("terminology", "Terminology"),
+    ("sakura", "Sakura"),
+    ("rio", "Rio"),
+    ("contour", "Contour"),
+    ("urxvt", "urxvt"),
+    ("urxvtd", "urxvt"),
+    ("xterm", "xterm"),
+    ("st", "st"),
+];
+
+/// Multiplexer servers, whose parent is init rather than the terminal, and the name of their
+/// client, which runs in the terminal.
+const MULTIPLEXERS: &[(&str, &str, &str)] = &[
+    ("tmux: server", "tmux: client", "tmux"),
+    ("SCREEN", "screen", "screen"),
+    ("zellij", "zellij", "zellij"),
+];
+
+/// Terminals that say who they are in the environment, for when the process tree can't tell,
+/// e.g. from inside a sandbox.
+const TERMINAL_VARIABLES: &[(&str, &str)] = &[
+    ("KITTY_WINDOW_ID", "kitty"),
+    ("ALACRITTY_WINDOW_ID", "Alacritty"),
+    ("WEZTERM_EXECUTABLE", "WezTerm"),
+    ("GHOSTTY_RESOURCES_DIR", "Ghostty"),
+    ("KONSOLE_VERSION", "Konsole"),
+    ("GNOME_TERMINAL_SCREEN", "GNOME Terminal"),
+];
+
+/// The name, parent and controlling terminal (0 for none) of a process, from
+/// `/proc/<pid>/stat`, e.g. "902 (fish) S 901 902 902 34816 ...". The name is in parentheses
+/// as it may contain spaces.
+fn process_stat(pid: &str) -> Option<(String, u32, u32)> {
+    let stat = fs::read_to_string(sys_path(&format!("/proc/{pid}/stat"))).ok()?;
+    let (start, end) = (stat.find('(')?, stat.rfind(')')?);
+    let mut fields = stat[end + 1..].split_whitespace();
+    let ppid = fields.nth(1)?.parse().ok()?;
+    let tty = fields.nth(2)?.parse().ok()?;
+
+    Some((stat[start + 1..end].to_string(), ppid, tty))
 }
 
-pub(crate) fn shell_name() -> String {
-    let shell = env::var("SHELL").expect("SHELL not set");
-    let parts: Vec<&str> = shell.split('/').collect();
-    parts.last().unwrap().to_string()
+/// The user running a process, who owns its `/proc/<pid>` directory.
+fn process_owner(pid: &str) -> Option<u32> {
+    fs::metadata(sys_path(&format!("/proc/{pid}")))
+        .ok()
+        .map(|metadata| metadata.uid())
 }
 
-pub(crate) fn terminal() -> String {
-    env::var("TERM").unwrap_or("N/A".to_string())
+/// Whether `$TMUX` or `$STY` names `server` as the one rsftch runs under, e.g.
+/// "/tmp/tmux-1000/default,1990,0" or "1990.pts-0.host". Zellij sets no such variable.
+fn is_our_server(multiplexer: &str, server: u32) -> bool {
+    let pid = match multiplexer {
+        "tmux" => env::var("TMUX")
+            .ok()
+            .and_then(|tmux| tmux.split(',').nth(1)?.parse().ok()),
+        "screen" => env::var("STY")
+            .ok()
+            .and_then(|sty| sty.split('.').next()?.parse().ok()),
+        _ => return true,
+    };
+
+    pid == Some(server)
+}
+
+/// The client attached to the multiplexer `server`: the one `client` process of the same user
+/// with a controlling terminal, which the server lacks. `None` when that isn't one process,
+/// e.g. with two terminals attached, or when `server` isn't the one rsftch runs under.
+fn multiplexer_client(server: u32, client: &str, multiplexer: &str) -> Option<u32> {
+    if !is_our_server(multiplexer, server) {
+        return None;
+    }
+
+    let owner = process_owner("self");
+    let mut clients = sorted_dir(&sys_path("/proc"))
+        .iter()
+        .filter_map(|path| path.file_name()?.to_str()?.parse::<u32>().ok())
+        .filter(|&pid| pid != server)
+        .filter(|pid| {
+            process_stat(&pid.to_string()).is_some_and(|(comm, _, tty)| comm == client && tty != 0)
+        })
+        .filter(|pid| process_owner(&pid.to_string()) == owner)
+        .collect::<Vec<_>>();
+
+    match clients.len() {
+        1 => clients.pop(),
+        _ => None,
+    }
+}
+
+/// Walks up the process tree from rsftch until a known terminal, noting any multiplexer on
+/// the way and going on from its attached client, and stopping at an SSH server. Falls back to
+/// `TERM_PROGRAM` and other variables terminals set, then to `$TERM`, unless the multiplexer's
+/// client is unknown, as those then describe whichever terminal started its server.
+pub fn terminal() -> Terminal {
+    let mut terminal = Terminal {
+        name: None,
+        multiplexer: None,
+        ssh: env::var_os("SSH_CONNECTION").is_some() || env::var_os("SSH_TTY").is_some(),
+    };
+    let mut client_unknown = false;
+
+    let mut pid = process_stat("self").map(|(_, ppid, _)| ppid);
+    // A cycle can't happen, but a bound keeps a corrupt tree from hanging the module.
+    for _ in 0..64 {
+        let Some((current, (comm, ppid, _))) = pid
+            .filter(|&pid| pid > 1)
+            .and_then(|pid| Some((pid, process_stat(&pid.to_string())?)))
+        else {
+            break;
+        };
+
+        if let Some((_, name)) = TERMINALS.iter().find(|(process, _)| *process == comm) {
+            terminal.name = Some(name.to_string());
+            break;
+        }
+
+        if comm == "sshd" || comm == "sshd-session" {
+            terminal.ssh = true;
+            break;
+        }
+
+        pid = match MULTIPLEXERS.iter().find(|(server, _, _)| *server == comm) {
+            Some((_, client, name)) => {
+                terminal.multiplexer.get_or_insert_with(|| name.to_string());
+                let Some(client) = multiplexer_client(current, client, name) else {
+                    client_unknown = true;
+                    break;
+                };
+                process_stat(&client.to_string()).map(|(_, ppid, _)| ppid)
+            }
+            None => Some(ppid),
+        };
+    }
+
+    if terminal.multiplexer.is_none() {
+        terminal.multiplexer = match env::var_os("TMUX").is_some() {
+            true => Some("tmux".to_string()),
+            false => env::var_os("STY").map(|_| "screen".to_string()),
+        };
+    }
+
+    if client_unknown {
+        return terminal;
+    }
+
+    terminal.name = terminal
+        .name
+        .or_else(|| {
+            env::var("TERM_PROGRAM")
+                .ok()
+                .filter(|program| !["tmux", "screen", "zellij"].contains(&program.as_str()))
+        })
+        .or_else(|| {
+            TERMINAL_VARIABLES
+                .iter()
+                .find(|(var, _)| env::var_os(var).is_some())
+                .map(|(_, name)| name.to_string())
+        })
+        .or_else(|| env::var("TERM").ok());
+
+    terminal
 }
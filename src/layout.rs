@@ -0,0 +1,86 @@
+/// Gap between the logo and the info sections when they are placed side by side.
+const GAP: &str = "  ";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LogoPosition {
+    Top,
+    Left,
+    Right,
+    None,
+}
+
+impl LogoPosition {
+    pub(crate) fn parse(position: &str) -> Option<LogoPosition> {
+        match position.to_lowercase().as_str() {
+            "top" => Some(LogoPosition::Top),
+            "left" => Some(LogoPosition::Left),
+            "right" => Some(LogoPosition::Right),
+            "none" | "off" => Some(LogoPosition::None),
+            _ => None,
+        }
+    }
+}
+
+/// A rendered line along with how many columns it takes up once escape codes are stripped.
+pub(crate) struct Line {
+    pub(crate) text: String,
+    pub(crate) width: usize,
+}
+
+impl Line {
+    /// Builds a line from `plain` text, and the same text once colored.
+    pub(crate) fn new(plain: &str, text: String) -> Line {
+        Line {
+            text,
+            width: plain.chars().count(),
+        }
+    }
+
+    pub(crate) fn empty() -> Line {
+        Line {
+            text: String::new(),
+            width: 0,
+        }
+    }
+}
+
+pub(crate) fn compose(logo: Vec<Line>, info: Vec<Line>, position: LogoPosition) -> Vec<String> {
+    match position {
+        LogoPosition::Top => logo
+            .into_iter()
+            .chain([Line::empty()])
+            .chain(info)
+            .map(|line| line.text)
+            .collect(),
+        LogoPosition::None => info.into_iter().map(|line| line.text).collect(),
+        LogoPosition::Left => side_by_side(logo, info),
+        LogoPosition::Right => side_by_side(info, logo),
+    }
+}
+
+/// Places two blocks next to each other, padding the left one to a common width and
+/// centering the shorter block vertically against the taller one.
+fn side_by_side(left: Vec<Line>, right: Vec<Line>) -> Vec<String> {
+    let left_width = left.iter().map(|line| line.width).max().unwrap_or(0);
+    let height = left.len().max(right.len());
+    let left_offset = (height - left.len()) / 2;
+    let right_offset = (height - right.len()) / 2;
+
+    (0..height)
+        .map(|row| {
+            let left_line = row.checked_sub(left_offset).and_then(|i| left.get(i));
+            let right_line = row.checked_sub(right_offset).and_then(|i| right.get(i));
+
+            let (left_text, left_len) = left_line.map_or(("", 0), |l| (l.text.as_str(), l.width));
+
+            match right_line {
+                Some(right_line) => format!(
+                    "{left_text}{}{GAP}{}",
+                    " ".repeat(left_width - left_len),
+                    right_line.text
+                ),
+                None => left_text.to_string(),
+            }
+        })
+        .collect()
+}
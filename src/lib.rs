@@ -0,0 +1,8 @@
+//! System detection behind the `rsftch` fetch tool.
+//!
+//! Every probe in [`info`] returns typed data (bytes, °C, counts) rather than text, so it can be
+//! embedded in other tools; the `rsftch` binary only formats and colors the results.
+
+pub mod ascii;
+pub mod info;
+pub mod sysroot;
@@ -0,0 +1,112 @@
+use crate::config::{get_logo, get_logo_colors, get_user_logo, parse_color};
+use crate::layout::Line;
+use colored::{Color, Colorize};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rsftch::ascii::{distro_ids, find_logo, BANNER};
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$\{c([1-6])\}").unwrap());
+
+/// The logo to print along with the colors its `${c1}` to `${c6}` placeholders map to.
+pub(crate) struct Logo {
+    pub(crate) text: String,
+    pub(crate) palette: Vec<Color>,
+}
+
+/// Picks the logo to print: the "logo" from the config, then for the distribution's `ID` and
+/// each of its `ID_LIKE`s in turn, its file in the user's logo directory or its built-in logo,
+/// and the Rsftch banner when nothing matches. Overriding the distribution skips the configured
+/// logo, as the point is to see another distribution's.
+///
+/// The palette is "logo_colors" from the config, then the built-in logo's own palette, and
+/// `fallback` (the first of "color") when neither is set.
+pub(crate) fn logo(
+    ascii_override: Option<String>,
+    custom_config_file: Option<String>,
+    ignore_config: bool,
+    fallback: Color,
+) -> Logo {
+    let configured = match ignore_config || ascii_override.is_some() {
+        true => None,
+        false => get_logo(custom_config_file.clone()),
+    };
+
+    let (text, builtin) = match configured {
+        Some(logo) => (logo, BANNER),
+        None => distro_ids(ascii_override)
+            .iter()
+            .find_map(|id| {
+                let user = match ignore_config {
+                    true => None,
+                    false => get_user_logo(id),
+                };
+
+                user.map(|logo| (logo, BANNER))
+                    .or_else(|| find_logo(id).map(|logo| (logo.ascii.to_string(), logo)))
+            })
+            .unwrap_or((BANNER.ascii.to_string(), BANNER)),
+    };
+
+    let configured_palette = match ignore_config {
+        true => None,
+        false => get_logo_colors(custom_config_file),
+    };
+    let builtin_palette = builtin
+        .palette
+        .iter()
+        .map(|color| parse_color(color).unwrap_or(Color::White))
+        .collect::<Vec<_>>();
+    let text = text.trim_end_matches('\n').to_string();
+
+    let palette = configured_palette
+        .filter(|palette| !palette.is_empty())
+        .or(Some(builtin_palette).filter(|palette| !palette.is_empty()))
+        .unwrap_or_else(|| vec![fallback]);
+
+    Logo { text, palette }
+}
+
+impl Logo {
+    /// Colors the logo line by line. A color carries on to the following lines until the next
+    /// placeholder, and placeholders past the end of the palette use its first color.
+    pub(crate) fn lines(&self) -> Vec<Line> {
+        let mut current = self.palette[0];
+
+        self.text
+            .lines()
+            .map(|line| {
+                let mut plain = String::new();
+                let mut colored = String::new();
+                let mut last = 0;
+
+                for captures in PLACEHOLDER.captures_iter(line) {
+                    let placeholder = captures.get(0).unwrap();
+                    let segment = &line[last..placeholder.start()];
+                    plain.push_str(segment);
+                    colored.push_str(&paint(segment, current));
+
+                    let index: usize = captures[1].parse().unwrap();
+                    current = self
+                        .palette
+                        .get(index - 1)
+                        .copied()
+                        .unwrap_or(self.palette[0]);
+                    last = placeholder.end();
+                }
+
+                let segment = &line[last..];
+                plain.push_str(segment);
+                colored.push_str(&paint(segment, current));
+
+                Line::new(&plain, colored)
+            })
+            .collect()
+    }
+}
+
+fn paint(segment: &str, color: Color) -> String {
+    match segment.is_empty() {
+        true => String::new(),
+        false => segment.color(color).to_string(),
+    }
+}
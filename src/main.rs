@@ -1,29 +1,47 @@
-use colored::Colorize;
+use colored::{Color, Colorize};
+use serde::Serialize;
 use std::env;
 use std::mem;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::process;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
 
-mod ascii;
+mod check;
 mod config;
-mod info;
+mod custom;
+mod format;
+mod layout;
+mod logo;
 
-use crate::ascii::*;
+use crate::check::check_config;
 use crate::config::*;
-use crate::info::*;
+use crate::custom::CustomModule;
+use crate::format::Value;
+use crate::layout::{compose, Line, LogoPosition};
+use crate::logo::logo;
+use rsftch::info::*;
+use rsftch::sysroot::set_sysroot;
 
 const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
 
 struct InfoItem {
-    title: &'static str,
-    icon: &'static str,
-    value: Arc<dyn Fn() -> String + Send + Sync>,
+    id: String,
+    title: String,
+    icon: String,
+    format: Option<String>,
+    /// One value per row, a module with none is left out, e.g. battery on a desktop.
+    value: Arc<dyn Fn() -> Vec<Value> + Send + Sync>,
 }
 
 impl Clone for InfoItem {
     fn clone(&self) -> Self {
         InfoItem {
-            title: self.title,
-            icon: self.icon,
+            id: self.id.clone(),
+            title: self.title.clone(),
+            icon: self.icon.clone(),
+            format: self.format.clone(),
             value: Arc::clone(&self.value),
         }
     }
@@ -34,12 +52,18 @@ fn main() {
     let mut ascii_override: Option<String> = None;
     let mut custom_config_file: Option<String> = None;
     let mut ignore_config: bool = false;
+    let mut json_output: bool = false;
+    let mut check_only: bool = false;
     let mut margin: i8 = 1;
+    let mut timeout_override: Option<u64> = None;
+    let mut logo_position: Option<LogoPosition> = None;
 
     for arg in 0..args.len() {
         match args[arg].to_lowercase().as_str() {
             "-h" | "--help" | "--usage" => return help(),
             "--ignore-config" => ignore_config = true,
+            "--json" => json_output = true,
+            "--check-config" => check_only = true,
             "-v" | "--version" => {
                 return println!("Rsftch {}\nMade by charklie", VERSION.unwrap_or("Unknown"));
             }
@@ -51,6 +75,28 @@ fn main() {
                     return help();
                 }
             }
+            "-t" | "--timeout" => match args.get(arg + 1).and_then(|t| t.parse().ok()) {
+                Some(timeout) => timeout_override = Some(timeout),
+                None => {
+                    println!(
+                        "[{}] Missing or invalid argument for timeout, expected milliseconds.\n",
+                        "ERROR".red()
+                    );
+                    return help();
+                }
+            },
+            "-l" | "--logo-position" => {
+                match args.get(arg + 1).and_then(|p| LogoPosition::parse(p)) {
+                    Some(position) => logo_position = Some(position),
+                    None => {
+                        println!(
+                            "[{}] Missing or invalid argument for logo position, expected top, left, right or none.\n",
+                            "ERROR".red()
+                        );
+                        return help();
+                    }
+                }
+            }
             "-o" | "--override" => {
                 if arg + 1 < args.len() && !args[arg + 1].starts_with("-") {
                     ascii_override = Some(mem::take(&mut args[arg + 1]));
@@ -73,200 +119,455 @@ fn main() {
                     return help();
                 }
             }
+            "--sysroot" => {
+                if arg + 1 < args.len() && !args[arg + 1].starts_with("-") {
+                    set_sysroot(PathBuf::from(mem::take(&mut args[arg + 1])));
+                } else {
+                    println!("[{}] Missing argument for sysroot.\n", "ERROR".red());
+                    return help();
+                }
+            }
             _ => {}
         };
     }
 
+    if check_only {
+        process::exit(match check_config(custom_config_file) {
+            true => 0,
+            false => 1,
+        });
+    }
+
     let infoitems = get_info_vecs(custom_config_file.clone(), ascii_override.clone());
+    let timeout = Duration::from_millis(
+        timeout_override.unwrap_or_else(|| get_timeout(custom_config_file.clone(), ignore_config)),
+    );
+    let sections = evaluate_info(infoitems, timeout);
+
+    if json_output {
+        return print_json(sections);
+    }
+
+    let logo_position = logo_position
+        .unwrap_or_else(|| get_logo_position(custom_config_file.clone(), ignore_config));
+
     print_info(
-        infoitems,
+        sections,
         margin,
         ascii_override,
         custom_config_file,
         ignore_config,
+        logo_position,
     );
 }
 
+fn help() {
+    println!(
+        r#"Usage: rsftch [-h / --help / --usage] [-v / --version] [-o / --override <distro name>] [-m / --margin <margin>] [-t / --timeout <milliseconds>] [-l / --logo-position <position>] [--ignore-config] [--config <absolute path to config>] [--sysroot <directory>] [--json] [--check-config]
+        
+      -h, --help, --usage         Bring up this menu.
+      -v, --version               Print version number.
+      -o, --override              Overrides distribution, affects ASCII and "distro" info. Running without an argument prints all possible options.
+      -m, --margin                Add margin to the info sections, default 1. E.g. `rsftch --info distro` would output: "EndeavourOS".
+      -t, --timeout               Milliseconds to wait for each info before showing "N/A", default 2000.
+      -l, --logo-position         Where to put the logo relative to the info: top (default), left, right or none.
+          --config                Specify another info config file to be used.
+          --ignore-config         Ignores configuration and uses the example one.
+          --sysroot               Read /proc, /sys and /etc from another directory, e.g. a captured system.
+          --json                  Print the configured info as JSON instead of the colored tree.
+          --check-config          Validate the configuration file and exit, non-zero if there are problems.
+
+    Configuration file is located at: ~/.config/rsftch/info.json"#
+    );
+}
+
+fn ascii_test() {
+    let distros = [
+        "Arch Linux",
+        "Debian",
+        "Fedora",
+        "EndeavourOS",
+        "Void",
+        "Ubuntu",
+        "Suse",
+        "Raspbian",
+        "Linux Mint",
+        "MX Linux",
+        "Gentoo",
+        "Funtoo",
+        "Slackware",
+        "UwUntu",
+        "NixOS",
+        "VanillaOS",
+        "Kali Linux",
+        "CachyOS",
+        "NetBSD",
+        "FreeBSD",
+        "Alpine Linux",
+        "Manjaro",
+        "CentOS",
+        "Rocky Linux",
+        "AlmaLinux",
+        "Unknown distro",
+    ];
+
+    for i in distros {
+        println!("\n{i}:");
+        for line in logo(Some(i.to_string()), None, true, Color::White).lines() {
+            println!("{}", line.text);
+        }
+    }
+}
+
 fn get_info_vecs(
     custom_config_file: Option<String>,
     ascii_override: Option<String>,
 ) -> Vec<Vec<InfoItem>> {
     let distro = InfoItem {
-        title: "distro",
-        icon: "",
+        id: "os".to_string(),
+        title: "distro".to_string(),
+        icon: "".to_string(),
+        format: None,
         value: Arc::new(move || {
-            os_pretty_name(ascii_override.clone(), "NAME")
-                .unwrap_or(uname("-s", ascii_override.clone()))
+            vec![format::text(
+                ascii_override
+                    .clone()
+                    .or_else(|| os_release("NAME"))
+                    .or_else(|| uname("-s")),
+            )]
         }),
     };
 
     let hostname = InfoItem {
-        title: "host",
-        icon: "󱩛",
-        value: Arc::new(move || uname("-n", None)),
+        id: "host".to_string(),
+        title: "host".to_string(),
+        icon: "󱩛".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::text(uname("-n"))]),
+    };
+
+    let network_config = get_network_config(custom_config_file.clone());
+    let network = InfoItem {
+        id: "network".to_string(),
+        title: "network".to_string(),
+        icon: "󰩟".to_string(),
+        format: None,
+        value: Arc::new(move || format::network(network_config.select(network_interfaces()))),
     };
 
     let shell = InfoItem {
-        title: "shell",
-        icon: "",
-        value: Arc::new(move || shell_name()),
+        id: "shell".to_string(),
+        title: "shell".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::text(shell_name())]),
     };
 
     let kernel = InfoItem {
-        title: "kernel",
-        icon: "",
-        value: Arc::new(move || uname("-r", None)),
+        id: "kernel".to_string(),
+        title: "kernel".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::text(uname("-r"))]),
     };
 
     let packs = InfoItem {
-        title: "packs",
-        icon: "󰿺",
-        value: Arc::new(move || packages()),
+        id: "packs".to_string(),
+        title: "packs".to_string(),
+        icon: "󰿺".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::packages(packages())]),
     };
 
     let user = InfoItem {
-        title: "user",
-        icon: "",
-        value: Arc::new(move || whoami()),
+        id: "user".to_string(),
+        title: "user".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::text(whoami())]),
     };
 
     let term = InfoItem {
-        title: "term",
-        icon: "",
-        value: Arc::new(move || terminal()),
+        id: "term".to_string(),
+        title: "term".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::terminal(terminal())]),
     };
 
     let de = InfoItem {
-        title: "de/wm",
-        icon: "",
-        value: Arc::new(move || wm()),
+        id: "de".to_string(),
+        title: "de/wm".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::desktop(desktop())]),
     };
 
     let cpu = InfoItem {
-        title: "cpu",
-        icon: "󰍛",
-        value: Arc::new(move || cpu_info()),
+        id: "cpu".to_string(),
+        title: "cpu".to_string(),
+        icon: "󰍛".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::cpu(cpu_info())]),
+    };
+
+    let cpu_usage_config = get_cpu_usage_config(custom_config_file.clone());
+    let cpu_usage = InfoItem {
+        id: "cpu-usage".to_string(),
+        title: "usage".to_string(),
+        icon: "󰓅".to_string(),
+        format: None,
+        value: Arc::new(move || {
+            let interval = Duration::from_millis(cpu_usage_config.interval);
+            format::cpu_usage(rsftch::info::cpu_usage(interval), cpu_usage_config.per_core)
+        }),
+    };
+
+    let load = InfoItem {
+        id: "load".to_string(),
+        title: "load".to_string(),
+        icon: "󰊚".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::load(load_average())]),
     };
 
     let mem = InfoItem {
-        title: "mem",
-        icon: "",
-        value: Arc::new(move || mem()),
+        id: "mem".to_string(),
+        title: "mem".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::memory(mem())]),
+    };
+
+    let swap = InfoItem {
+        id: "swap".to_string(),
+        title: "swap".to_string(),
+        icon: "󰾴".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::swap(swap())]),
     };
 
     let res = InfoItem {
-        title: "res",
-        icon: "",
-        value: Arc::new(move || res()),
+        id: "res".to_string(),
+        title: "res".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| format::displays(res())),
     };
 
     let uptime = InfoItem {
-        title: "uptime",
-        icon: "󰄉",
-        value: Arc::new(move || uptime()),
+        id: "uptime".to_string(),
+        title: "uptime".to_string(),
+        icon: "󰄉".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::duration(uptime())]),
     };
 
     let gpu = InfoItem {
-        title: "gpu",
-        icon: "󰍹",
-        value: Arc::new(move || gpu_info()),
+        id: "gpu".to_string(),
+        title: "gpu".to_string(),
+        icon: "󰍹".to_string(),
+        format: None,
+        value: Arc::new(|| format::gpus(gpus())),
     };
 
+    let disk_config = get_disk_config(custom_config_file.clone());
     let disk = InfoItem {
-        title: "disk",
-        icon: "",
-        value: Arc::new(move || disk_usage()),
+        id: "disk".to_string(),
+        title: "disk".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(move || format::disks(disk_usage(&disk_config.mounts))),
+    };
+
+    let battery = InfoItem {
+        id: "battery".to_string(),
+        title: "battery".to_string(),
+        icon: "󰁹".to_string(),
+        format: None,
+        value: Arc::new(|| format::batteries(batteries())),
     };
 
     let timezone = InfoItem {
-        title: "timezone",
-        icon: "󰥔",
-        value: Arc::new(move || timezone()),
+        id: "time".to_string(),
+        title: "timezone".to_string(),
+        icon: "󰥔".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::text(timezone())]),
     };
 
     let empty = InfoItem {
-        title: "empty",
-        icon: "",
-        value: Arc::new(move || String::new()),
+        id: "empty".to_string(),
+        title: "empty".to_string(),
+        icon: "".to_string(),
+        format: None,
+        value: Arc::new(|| vec![format::text(Some(String::new()))]),
     };
 
-    let info_vecs: Vec<Vec<String>> = parse_json_to_vec(custom_config_file.clone());
+    let custom_modules = get_custom_modules(custom_config_file.clone());
+    let info_vecs: Vec<Vec<ModuleConfig>> = parse_json_to_vec(custom_config_file.clone());
 
     info_vecs
         .iter()
         .map(|inner_list| {
             inner_list
                 .iter()
-                .map(|c| match c.to_lowercase().as_str() {
-                    "os" | "distro" => distro.clone(),
-                    "host" | "hostname" => hostname.clone(),
-                    "shell" => shell.clone(),
-                    "kernel" => kernel.clone(),
-                    "packs" | "packages" => packs.clone(),
-                    "user" | "username" => user.clone(),
-                    "term" | "terminal" => term.clone(),
-                    "de" | "dewm" | "wm" => de.clone(),
-                    "cpu" | "processor" => cpu.clone(),
-                    "gpu" | "graphics" => gpu.clone(),
-                    "mem" | "memory" => mem.clone(),
-                    "uptime" => uptime.clone(),
-                    "res" | "display" | "resolution" => res.clone(),
-                    "time" | "timezone" => timezone.clone(),
-                    "disk" | "diskusage" => disk.clone(),
-                    _ => empty.clone(),
+                .map(|config| {
+                    let item = match canonical_module(&config.module) {
+                        Some("os") => distro.clone(),
+                        Some("host") => hostname.clone(),
+                        Some("network") => network.clone(),
+                        Some("shell") => shell.clone(),
+                        Some("kernel") => kernel.clone(),
+                        Some("packs") => packs.clone(),
+                        Some("user") => user.clone(),
+                        Some("term") => term.clone(),
+                        Some("de") => de.clone(),
+                        Some("cpu") => cpu.clone(),
+                        Some("cpu-usage") => cpu_usage.clone(),
+                        Some("load") => load.clone(),
+                        Some("gpu") => gpu.clone(),
+                        Some("mem") => mem.clone(),
+                        Some("swap") => swap.clone(),
+                        Some("uptime") => uptime.clone(),
+                        Some("res") => res.clone(),
+                        Some("time") => timezone.clone(),
+                        Some("disk") => disk.clone(),
+                        Some("battery") => battery.clone(),
+                        _ => {
+                            let name = config.module.to_lowercase();
+                            custom_modules
+                                .get(&name)
+                                .map(|module| custom_info_item(&name, module))
+                                .unwrap_or_else(|| empty.clone())
+                        }
+                    };
+
+                    InfoItem {
+                        title: config.title.clone().unwrap_or(item.title),
+                        icon: config.icon.clone().unwrap_or(item.icon),
+                        format: config.format.clone(),
+                        ..item
+                    }
                 })
                 .collect()
         })
         .collect()
 }
 
-fn color(
-    ascii: String,
-    custom_config_file: Option<String>,
-    idx: usize,
-    ignore_config: bool,
-) -> String {
-    let colors = get_colors(custom_config_file, ignore_config);
+fn custom_info_item(name: &str, module: &CustomModule) -> InfoItem {
+    let module = module.clone();
 
-    if idx >= colors.len() {
-        eprintln!("[{}] Not the same amount of info sections as colors, make sure that there is one more color than there are info sections, or try using an example listed in the \"example/\" folder in the github repository.", "ERROR".red());
+    InfoItem {
+        id: name.to_string(),
+        title: module.title.clone().unwrap_or_else(|| name.to_string()),
+        icon: module.icon.clone(),
+        format: None,
+        value: Arc::new(move || vec![format::text(module.value())]),
     }
+}
+
+/// Runs every module on its own thread so a slow probe can't hold up the rest,
+/// anything that hasn't answered once `timeout` has passed is shown as "N/A".
+/// Returns each section's rows along with the module they belong to.
+fn evaluate_info(infos: Vec<Vec<InfoItem>>, timeout: Duration) -> Vec<Vec<(InfoItem, String)>> {
+    let receivers: Vec<Vec<mpsc::Receiver<Vec<String>>>> = infos
+        .iter()
+        .map(|section| {
+            section
+                .iter()
+                .map(|infoitem| {
+                    let (sender, receiver) = mpsc::channel();
+                    let value = Arc::clone(&infoitem.value);
+                    let format = infoitem.format.clone();
+                    thread::spawn(move || {
+                        let rows = value()
+                            .iter()
+                            .map(|value| value.render(format.as_deref()))
+                            .collect();
+                        let _ = sender.send(rows);
+                    });
+                    receiver
+                })
+                .collect()
+        })
+        .collect();
+
+    let deadline = Instant::now() + timeout;
+
+    infos
+        .into_iter()
+        .zip(receivers)
+        .map(|(section, section_receivers)| {
+            section
+                .into_iter()
+                .zip(section_receivers)
+                .flat_map(|(infoitem, receiver)| {
+                    receiver
+                        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                        .unwrap_or_else(|_| vec![format::NOT_AVAILABLE.to_string()])
+                        .into_iter()
+                        .map(move |row| (infoitem.clone(), row))
+                })
+                .collect()
+        })
+        .collect()
+}
 
-    ascii.color(colors[idx]).to_string()
+fn color(ascii: String, colors: &[Color], idx: usize) -> String {
+    ascii
+        .color(colors.get(idx).copied().unwrap_or(Color::White))
+        .to_string()
 }
 
 fn print_info(
-    infos: Vec<Vec<InfoItem>>,
+    sections: Vec<Vec<(InfoItem, String)>>,
     margin: i8,
     ascii_override: Option<String>,
     custom_config_file: Option<String>,
     ignore_config: bool,
+    logo_position: LogoPosition,
 ) {
-    let longest_title = infos
+    let longest_title = sections
         .iter()
         .flat_map(|inner| inner.iter())
-        .map(|s| s.title.len())
+        .map(|(s, _)| s.title.chars().count())
         .max()
         .unwrap_or(0);
 
-    let distro_ascii = get_distro_ascii(ascii_override.clone());
-    println!(
-        "{}\n",
-        color(distro_ascii, ascii_override, 0, ignore_config)
-    );
+    let colors = get_colors(custom_config_file.clone(), ignore_config);
 
-    infos
-        .clone()
-        .into_iter()
+    if colors.len() < sections.len() + 1 {
+        eprintln!("[{}] Not the same amount of info sections as colors, make sure that there is one more color than there are info sections, or try using an example listed in the \"example/\" folder in the github repository.", "ERROR".red());
+    }
+
+    let logo_lines = match logo_position {
+        LogoPosition::None => Vec::new(),
+        _ => logo(
+            ascii_override,
+            custom_config_file,
+            ignore_config,
+            colors.first().copied().unwrap_or(Color::White),
+        )
+        .lines(),
+    };
+
+    let mut info_lines = Vec::new();
+
+    sections
+        .iter()
         .enumerate()
+        .filter(|(_, section)| !section.is_empty())
         .for_each(|(idx, section)| {
+            if !info_lines.is_empty() {
+                info_lines.push(Line::empty());
+            }
+
             section
-                .clone()
-                .into_iter()
+                .iter()
                 .enumerate()
-                .for_each(|(idx2, infoitem)| {
-                    let simple_color =
-                        |s| color(s, custom_config_file.clone(), idx + 1, ignore_config);
+                .for_each(|(idx2, (infoitem, value))| {
+                    let simple_color = |s| color(s, &colors, idx + 1);
 
                     let connector = match idx2 {
                         0 => "╭─",
@@ -275,21 +576,67 @@ fn print_info(
                     }
                     .to_string();
 
-                    let alignment_space = " ".repeat(longest_title - infoitem.title.len());
+                    let alignment_space =
+                        " ".repeat(longest_title - infoitem.title.chars().count());
                     let margin_space = " ".repeat(margin as usize);
 
-                    println!(
-                        "{margin_space}{}{}  {}{alignment_space} {} {}",
-                        simple_color(connector),
-                        simple_color(infoitem.icon.to_string()),
-                        infoitem.title,
-                        simple_color("~>".to_string()),
-                        (infoitem.value)()
+                    let plain = format!(
+                        "{margin_space}{connector}{}  {}{alignment_space} ~> {value}",
+                        infoitem.icon, infoitem.title,
                     );
-                });
 
-            if idx != infos.len() - 1 {
-                println!();
-            }
+                    info_lines.push(Line::new(
+                        &plain,
+                        format!(
+                            "{margin_space}{}{}  {}{alignment_space} {} {}",
+                            simple_color(connector),
+                            simple_color(infoitem.icon.clone()),
+                            infoitem.title,
+                            simple_color("~>".to_string()),
+                            value
+                        ),
+                    ));
+                });
         });
+
+    for line in compose(logo_lines, info_lines, logo_position) {
+        println!("{line}");
+    }
+}
+
+#[derive(Serialize)]
+struct JsonModule {
+    module: String,
+    title: String,
+    icon: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct JsonOutput {
+    sections: Vec<Vec<JsonModule>>,
+}
+
+fn print_json(sections: Vec<Vec<(InfoItem, String)>>) {
+    let output = JsonOutput {
+        sections: sections
+            .into_iter()
+            .map(|section| {
+                section
+                    .into_iter()
+                    .map(|(infoitem, value)| JsonModule {
+                        module: infoitem.id,
+                        title: infoitem.title,
+                        icon: infoitem.icon,
+                        value,
+                    })
+                    .collect()
+            })
+            .collect(),
+    };
+
+    match serde_json::to_string_pretty(&output) {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("[{}] Could not serialize info to JSON: {e}", "ERROR".red()),
+    }
 }
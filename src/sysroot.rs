@@ -0,0 +1,24 @@
+use once_cell::sync::OnceCell;
+use std::path::{Path, PathBuf};
+
+static SYSROOT: OnceCell<PathBuf> = OnceCell::new();
+
+/// Points every /proc, /sys and /etc read at `root` instead of `/`, used to replay captured
+/// filesystem trees. Only the first call has any effect.
+pub fn set_sysroot(root: PathBuf) {
+    let _ = SYSROOT.set(root);
+}
+
+/// Resolves an absolute system path against the configured sysroot.
+pub fn sys_path(path: &str) -> PathBuf {
+    match SYSROOT.get() {
+        Some(root) => root.join(path.trim_start_matches('/')),
+        None => Path::new(path).to_path_buf(),
+    }
+}
+
+/// Whether a sysroot is set, in which case network addresses are read from the sysroot's
+/// procfs instead of getifaddrs, and resolutions only from its DRM connectors.
+pub fn has_sysroot() -> bool {
+    SYSROOT.get().is_some()
+}
@@ -0,0 +1,57 @@
+use std::{path::PathBuf, process::Command};
+
+fn check(config: &str) -> (bool, String) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(config);
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--check-config")
+        .arg("--config")
+        .arg(path)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to run rsftch");
+
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn valid_config() {
+    let (ok, output) = check("info.json");
+    assert!(ok, "{output}");
+    assert!(output.contains("[OK]"));
+}
+
+#[test]
+fn reports_syntax_error_position() {
+    let (ok, output) = check("configs/trailing-comma.json");
+    assert!(!ok);
+    assert!(
+        output.contains("trailing-comma.json:2:17: trailing comma"),
+        "{output}"
+    );
+}
+
+#[test]
+fn reports_unknown_modules_and_aliases() {
+    let (ok, output) = check("configs/unknown-module.json");
+    assert!(!ok);
+    assert!(output.contains("unknown module \"cpuu\""), "{output}");
+    assert!(
+        output.contains("\"processor\" is an alias for \"cpu\""),
+        "{output}"
+    );
+}
+
+#[test]
+fn reports_cpu_usage_interval_past_timeout() {
+    let (ok, output) = check("configs/slow-cpu-usage.json");
+    assert!(!ok);
+    assert!(
+        output.contains("interval of 1000ms is not shorter than the 500ms timeout"),
+        "{output}"
+    );
+}
@@ -0,0 +1,30 @@
+//! User-defined modules from `tests/fixtures/configs/custom-modules.json`.
+
+use std::{path::PathBuf, process::Command};
+
+#[test]
+fn command_file_and_regex_modules() {
+    let configs = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/configs");
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--config")
+        .arg(configs.join("custom-modules.json"))
+        .args(["--logo-position", "none"])
+        .env("NO_COLOR", "1")
+        .env("HOME", configs.join("custom"))
+        .output()
+        .expect("failed to run rsftch");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        concat!(
+            " ╭─  greeting ~> hello world\n",
+            " ├─  version  ~> 0.6\n",
+            " ├─  release  ~> 1.2.3\n",
+            " ├─  build    ~> nightly 2026-10-18\n",
+            " ├─  motd     ~> Welcome home\n",
+            " ├─  broken   ~> N/A\n",
+            " ╰─  missing  ~> N/A\n",
+        )
+    );
+}
@@ -0,0 +1,37 @@
+//! Disk detection against the mount table in `tests/fixtures/fedora`. The sizes come from
+//! whatever filesystem the fixtures live on, so only their sanity is checked.
+
+use rsftch::{info::disk_usage, sysroot::set_sysroot};
+use std::path::PathBuf;
+
+#[test]
+fn reads_requested_mounts_in_order() {
+    set_sysroot(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fedora"));
+
+    let mounts = ["/mnt/backup drive", "/", "/proc", "/var", "/home"].map(str::to_string);
+    let disks = disk_usage(&mounts);
+
+    let found: Vec<(&str, &str, &str)> = disks
+        .iter()
+        .map(|disk| {
+            (
+                disk.mount_point.as_str(),
+                disk.filesystem.as_str(),
+                disk.fs_type.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("/mnt/backup drive", "/dev/sda1", "ext4"),
+            ("/", "/dev/nvme0n1p3", "btrfs"),
+            ("/home", "/dev/nvme0n1p3", "btrfs"),
+        ]
+    );
+
+    for disk in disks {
+        assert!(disk.total > 0);
+        assert!(disk.used <= disk.total && disk.percent_used() <= 100);
+    }
+}
@@ -0,0 +1 @@
+[ { meta = { description = "Nix package manager"; }; name = "nix-2.24.9"; out = { outPath = "/nix/store/abc-nix-2.24.9"; }; outPath = "/nix/store/abc-nix-2.24.9"; outputs = [ "out" ]; system = "x86_64-linux"; type = "derivation"; } { meta = { }; name = "nss-cacert-3.104"; out = { outPath = "/nix/store/def-nss-cacert-3.104"; }; outPath = "/nix/store/def-nss-cacert-3.104"; outputs = [ "out" ]; system = "x86_64-linux"; type = "derivation"; } ]
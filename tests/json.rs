@@ -0,0 +1,40 @@
+//! Checks `--json` against the captured Fedora system in `tests/fixtures/fedora`.
+
+use serde_json::Value;
+use std::{fs, path::PathBuf, process::Command};
+
+#[test]
+fn json_output_matches_fixture() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--sysroot")
+        .arg(fixtures.join("fedora"))
+        .arg("--config")
+        .arg(fixtures.join("configs/json.json"))
+        .arg("--json")
+        .output()
+        .expect("failed to run rsftch");
+    assert!(output.status.success());
+
+    let rendered: Value = serde_json::from_slice(&output.stdout).expect("invalid JSON");
+    let expected: Value =
+        serde_json::from_str(&fs::read_to_string(fixtures.join("fedora/expected.json")).unwrap())
+            .unwrap();
+
+    for module in rendered["sections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|section| section.as_array().unwrap())
+    {
+        let mut keys: Vec<&str> = module
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        assert_eq!(keys, ["icon", "module", "title", "value"]);
+    }
+    assert_eq!(rendered, expected);
+}
@@ -0,0 +1,62 @@
+//! Which colors the logo is printed in, checked on the escape codes of the first logo line.
+
+use std::{path::PathBuf, process::Command};
+
+fn first_line(args: &[&str]) -> String {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--sysroot")
+        .arg(fixtures.join("arch"))
+        .args(args)
+        .current_dir(&fixtures)
+        .env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .env("COLORTERM", "truecolor")
+        .env("XDG_CONFIG_HOME", &fixtures)
+        .output()
+        .expect("failed to run rsftch");
+
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[test]
+fn built_in_logo_uses_its_palette() {
+    let line = first_line(&["--config", "info.json"]);
+    assert!(line.starts_with("\x1b[38;2;23;147;209m"), "{line:?}");
+}
+
+#[test]
+fn built_in_palette_fills_in_the_placeholders() {
+    let line = first_line(&["--config", "info.json", "--override", "nixos"]);
+    assert!(line.starts_with("\x1b[38;2;126;186;228m"), "{line:?}");
+    assert!(line.contains("\x1b[38;2;82;119;195m"), "{line:?}");
+}
+
+#[test]
+fn first_color_colors_the_banner() {
+    let line = first_line(&["--config", "info.json", "--override", "unknown"]);
+    assert!(line.starts_with("\x1b[31m"), "{line:?}");
+}
+
+#[test]
+fn first_color_colors_user_logos() {
+    let line = first_line(&["--config", "info.json", "--override", "acme"]);
+    assert!(line.starts_with("\x1b[31m"), "{line:?}");
+}
+
+#[test]
+fn logo_colors_replace_the_palette() {
+    let line = first_line(&["--config", "configs/logo-colors.json"]);
+    assert!(line.starts_with("\x1b[33m"), "{line:?}");
+}
+
+#[test]
+fn default_config_uses_the_built_in_palette() {
+    let line = first_line(&["--ignore-config"]);
+    assert!(line.starts_with("\x1b[38;2;23;147;209m"), "{line:?}");
+}
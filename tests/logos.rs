@@ -0,0 +1,119 @@
+//! Matching of os-release IDs and `--override` names to the built-in logos.
+
+use rsftch::ascii::{find_logo, BANNER};
+use std::{env, fs, process::Command};
+
+fn logo(id: &str) -> Option<&'static str> {
+    find_logo(id).map(|logo| logo.ascii)
+}
+
+#[test]
+fn matches_os_release_ids_exactly() {
+    for id in [
+        "arch",
+        "debian",
+        "fedora",
+        "endeavouros",
+        "void",
+        "ubuntu",
+        "opensuse-tumbleweed",
+        "opensuse-leap",
+        "raspbian",
+        "linuxmint",
+        "gentoo",
+        "slackware",
+        "nixos",
+        "kali",
+        "cachyos",
+        "netbsd",
+        "freebsd",
+        "alpine",
+        "manjaro",
+        "centos",
+        "rocky",
+        "almalinux",
+        "artix",
+        "solus",
+        "rhel",
+        "zorin",
+        "nobara",
+        "bazzite",
+        "elementary",
+        "opensuse-aeon",
+        "opensuse-kalpa",
+    ] {
+        assert!(logo(id).is_some(), "no logo for {id}");
+    }
+
+    assert_eq!(logo("opensuse-leap"), logo("opensuse-tumbleweed"));
+}
+
+#[test]
+fn does_not_match_substrings() {
+    for id in [
+        "pop",
+        "garuda",
+        "archcraft",
+        "nixie",
+        "linux",
+        "mxlinuxish",
+        "",
+    ] {
+        assert!(logo(id).is_none(), "{id} should have no logo of its own");
+    }
+}
+
+#[test]
+fn override_names_ignore_case_and_punctuation() {
+    assert_eq!(logo("Arch Linux"), logo("arch"));
+    assert_eq!(logo("Linux Mint"), logo("linuxmint"));
+    assert_eq!(logo("MX Linux"), logo("mx"));
+    assert_eq!(logo("Rocky-Linux"), logo("rocky"));
+    assert_eq!(logo("NixOS"), logo("nixos"));
+}
+
+/// The logo printed for a system whose os-release has `id` and `id_like`.
+fn logo_for(id: &str, id_like: &str) -> String {
+    let root = env::temp_dir().join(format!("rsftch-logos-{id}"));
+    fs::create_dir_all(root.join("etc")).unwrap();
+    fs::write(
+        root.join("etc/os-release"),
+        format!("ID={id}\nID_LIKE=\"{id_like}\"\n"),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--sysroot")
+        .arg(&root)
+        .arg("--ignore-config")
+        .args(["--logo-position", "top"])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to run rsftch");
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn derivatives_get_their_parents_logo() {
+    for (id, id_like, parent) in [
+        ("pop", "ubuntu debian", "ubuntu"),
+        ("garuda", "arch", "arch"),
+        ("neon", "ubuntu debian", "ubuntu"),
+        ("ultramarine", "fedora", "fedora"),
+        ("opensuse-aeon-next", "opensuse-microos suse", "opensuse"),
+    ] {
+        let parent = logo(parent).unwrap();
+        assert!(
+            logo_for(id, id_like).starts_with(parent),
+            "{id} should get the {id_like} logo"
+        );
+    }
+}
+
+#[test]
+fn unknown_distros_get_the_banner() {
+    assert!(logo_for("acmeos", "acme").starts_with(BANNER.ascii));
+}
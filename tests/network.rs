@@ -0,0 +1,29 @@
+//! Network detection on the live system, which reads its addresses with `getifaddrs`.
+
+use rsftch::info::network_interfaces;
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::Path,
+};
+
+#[test]
+fn loopback_has_its_own_prefix() {
+    if !Path::new("/sys/class/net/lo").exists() {
+        return;
+    }
+
+    let interfaces = network_interfaces();
+    let lo = interfaces
+        .iter()
+        .find(|interface| interface.name == "lo")
+        .expect("lo is missing");
+
+    assert!(lo.loopback);
+    let ipv4 = lo
+        .addresses
+        .iter()
+        .find(|ip| ip.address == IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .expect("lo has no 127.0.0.1");
+    assert_eq!(ipv4.prefix, 8);
+    assert!(lo.addresses.is_sorted_by_key(|ip| ip.address.is_ipv6()));
+}
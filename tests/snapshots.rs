@@ -0,0 +1,297 @@
+//! Renders rsftch against the captured systems in `tests/fixtures` and compares the output
+//! with each fixture's `expected.txt`. Run with `UPDATE_SNAPSHOTS=1` to rewrite them.
+
+use std::{env, fs, path::PathBuf, process::Command};
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn render(distro: &str, args: &[&str], envs: &[(&str, &str)]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--sysroot")
+        .arg(fixtures().join(distro))
+        .arg("--config")
+        .arg(fixtures().join("info.json"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .env("XDG_CONFIG_HOME", fixtures())
+        .env("HOME", "/home/user")
+        .env_remove("CARGO_HOME")
+        .env_remove("DISPLAY")
+        .env_remove("WAYLAND_DISPLAY")
+        .env_remove("XDG_SESSION_TYPE")
+        .env_remove("XDG_CURRENT_DESKTOP")
+        .env_remove("XDG_SESSION_DESKTOP")
+        .env_remove("DESKTOP_SESSION")
+        .env_remove("TERM_PROGRAM")
+        .env_remove("TMUX")
+        .env_remove("STY")
+        .env_remove("SSH_CONNECTION")
+        .env_remove("SSH_TTY")
+        .envs(envs.iter().copied())
+        .output()
+        .expect("failed to run rsftch");
+
+    assert!(
+        output.status.success(),
+        "rsftch exited with {}",
+        output.status
+    );
+    String::from_utf8(output.stdout).expect("rsftch printed invalid UTF-8")
+}
+
+fn assert_snapshot(distro: &str) {
+    assert_snapshot_named(distro, "expected.txt", &[]);
+}
+
+fn assert_snapshot_named(distro: &str, name: &str, args: &[&str]) {
+    assert_snapshot_with_env(distro, name, args, &[]);
+}
+
+fn assert_snapshot_with_env(distro: &str, name: &str, args: &[&str], envs: &[(&str, &str)]) {
+    let rendered = render(distro, args, envs);
+    let snapshot = fixtures().join(distro).join(name);
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&snapshot, &rendered).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&snapshot).unwrap_or_default();
+    assert_eq!(rendered, expected, "snapshot mismatch for {distro}");
+}
+
+/// Renders only the modules of `configs/<config>` against `distro`, without the logo.
+fn assert_module_snapshot(distro: &str, config: &str, name: &str, envs: &[(&str, &str)]) {
+    let config = fixtures().join("configs").join(config);
+    let args = [
+        "--config",
+        config.to_str().unwrap(),
+        "--logo-position",
+        "none",
+    ];
+
+    assert_snapshot_with_env(distro, name, &args, envs);
+}
+
+#[test]
+fn arch() {
+    assert_snapshot("arch");
+}
+
+#[test]
+fn debian() {
+    assert_snapshot("debian");
+}
+
+#[test]
+fn fedora() {
+    assert_snapshot("fedora");
+}
+
+#[test]
+fn ubuntu() {
+    assert_snapshot("ubuntu");
+}
+
+#[test]
+fn alpine() {
+    assert_snapshot("alpine");
+}
+
+#[test]
+fn user_logo_directory() {
+    assert_snapshot("acme");
+}
+
+#[test]
+fn logo_left() {
+    assert_snapshot_named("arch", "expected-left.txt", &["--logo-position", "left"]);
+}
+
+#[test]
+fn logo_right() {
+    assert_snapshot_named(
+        "debian",
+        "expected-right.txt",
+        &["--logo-position", "right"],
+    );
+}
+
+#[test]
+fn logo_placeholders_are_stripped() {
+    assert_snapshot_named("arch", "expected-nixos.txt", &["--override", "nixos"]);
+}
+
+#[test]
+fn parent_logo_from_id_like() {
+    assert_snapshot("pop");
+}
+
+#[test]
+fn network_interfaces_from_config() {
+    assert_module_snapshot("arch", "network.json", "expected-network.txt", &[]);
+}
+
+#[test]
+fn cpu_topology_and_frequency() {
+    for distro in ["arch", "debian", "ubuntu"] {
+        assert_module_snapshot(distro, "cpu.json", "expected-cpu.txt", &[]);
+    }
+}
+
+#[test]
+fn load_and_cpu_usage() {
+    assert_module_snapshot("arch", "load.json", "expected-load.txt", &[]);
+    assert_module_snapshot(
+        "arch",
+        "load-per-core.json",
+        "expected-load-per-core.txt",
+        &[],
+    );
+    assert_module_snapshot("debian", "load.json", "expected-load.txt", &[]);
+}
+
+#[test]
+fn packages_from_databases() {
+    for distro in ["arch", "debian", "alpine", "void", "gentoo"] {
+        assert_module_snapshot(distro, "packs.json", "expected-packs.txt", &[]);
+    }
+}
+
+#[test]
+fn packages_per_source() {
+    assert_module_snapshot("ubuntu", "packs.json", "expected-packs.txt", &[]);
+}
+
+#[test]
+fn desktop_from_processes() {
+    let cases: [(&str, &[(&str, &str)]); 5] = [
+        ("arch", &[("WAYLAND_DISPLAY", "wayland-1")]),
+        (
+            "fedora",
+            &[
+                ("XDG_SESSION_TYPE", "wayland"),
+                ("XDG_CURRENT_DESKTOP", "GNOME"),
+            ],
+        ),
+        ("debian", &[("DISPLAY", ":0")]),
+        ("ubuntu", &[]),
+        (
+            "pop",
+            &[("DISPLAY", ":0"), ("XDG_CURRENT_DESKTOP", "X-Cinnamon")],
+        ),
+    ];
+
+    for (distro, envs) in cases {
+        assert_module_snapshot(distro, "desktop.json", "expected-desktop.txt", envs);
+    }
+
+    assert_module_snapshot("debian", "desktop.json", "expected-desktop-tty.txt", &[]);
+    assert_module_snapshot(
+        "debian",
+        "desktop.json",
+        "expected-desktop-ssh.txt",
+        &[
+            ("DISPLAY", ":0"),
+            ("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.5 22"),
+        ],
+    );
+}
+
+#[test]
+fn terminal_from_process_tree() {
+    let term = [("TERM", "xterm-256color")];
+
+    for distro in ["arch", "fedora", "pop"] {
+        assert_module_snapshot(distro, "terminal.json", "expected-terminal.txt", &term);
+    }
+    assert_module_snapshot(
+        "debian",
+        "terminal.json",
+        "expected-terminal.txt",
+        &[
+            ("TERM", "xterm-256color"),
+            ("TMUX", "/tmp/tmux-1000/default,1990,0"),
+        ],
+    );
+    // Without $TMUX naming its server there's no telling which client is ours.
+    assert_module_snapshot(
+        "debian",
+        "terminal.json",
+        "expected-terminal-unknown.txt",
+        &term,
+    );
+    assert_module_snapshot(
+        "ubuntu",
+        "terminal.json",
+        "expected-terminal.txt",
+        &[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")],
+    );
+}
+
+#[test]
+fn module_overrides() {
+    for distro in ["arch", "debian"] {
+        assert_module_snapshot(distro, "overrides.json", "expected-overrides.txt", &[]);
+    }
+}
+
+#[test]
+fn battery_from_power_supply() {
+    for distro in ["debian", "fedora", "arch"] {
+        assert_module_snapshot(distro, "battery.json", "expected-battery.txt", &[]);
+    }
+}
+
+#[test]
+fn default_network_interfaces() {
+    assert_module_snapshot(
+        "arch",
+        "network-default.json",
+        "expected-network-default.txt",
+        &[],
+    );
+}
+
+#[test]
+fn swap_and_zram() {
+    for distro in ["arch", "debian", "fedora", "ubuntu"] {
+        assert_module_snapshot(distro, "swap.json", "expected-swap.txt", &[]);
+    }
+}
+
+#[test]
+fn cpu_temperature() {
+    for distro in ["alpine", "arch", "debian", "fedora", "ubuntu"] {
+        assert_module_snapshot(
+            distro,
+            "cpu-temperature.json",
+            "expected-cpu-temperature.txt",
+            &[],
+        );
+    }
+}
+
+#[test]
+fn gpus_from_sysfs() {
+    for distro in ["arch", "debian", "fedora"] {
+        assert_module_snapshot(distro, "gpu.json", "expected-gpu.txt", &[]);
+    }
+}
+
+#[test]
+fn resolutions_from_drm() {
+    for distro in ["arch", "debian", "fedora"] {
+        assert_module_snapshot(distro, "res.json", "expected-res.txt", &[]);
+    }
+
+    // Without DRM connectors a sysroot never asks the host's wlr-randr or xrandr.
+    assert_module_snapshot(
+        "debian",
+        "res.json",
+        "expected-res.txt",
+        &[("WAYLAND_DISPLAY", "wayland-1"), ("DISPLAY", ":0")],
+    );
+}
@@ -0,0 +1,47 @@
+//! Modules that take longer than the timeout are shown as N/A without holding up the rest.
+
+use std::{
+    path::PathBuf,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+#[test]
+fn slow_module_is_not_available_within_the_timeout() {
+    let started = Instant::now();
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .arg("--sysroot")
+        .arg(fixtures().join("arch"))
+        .arg("--config")
+        .arg(fixtures().join("configs/slow-module.json"))
+        .args(["--logo-position", "none"])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to run rsftch");
+    let elapsed = started.elapsed();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("Arch Linux"), "{stdout}");
+    assert!(stdout.contains("slow   ~> N/A"), "{stdout}");
+    assert!(elapsed < Duration::from_secs(2), "took {elapsed:?}");
+}
+
+#[test]
+fn invalid_timeout_is_reported() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsftch"))
+        .args(["--timeout", "abc"])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to run rsftch");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("[ERROR] Missing or invalid argument for timeout"),
+        "{stdout}"
+    );
+}